@@ -1,5 +1,15 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+use crate::libraries::big_num::{U1024, U128, U256, U512};
+use crate::libraries::fixed_point_64;
+use crate::libraries::full_math::MulDiv;
+use crate::libraries::tick_array_bit_map::{self, TickArryBitmap, TICK_ARRAY_BITMAP_SIZE};
+use crate::libraries::tick_math;
+use crate::pda;
+
+pub const AMM_CONFIG_SEED: &str = "amm_config";
+
 #[account]
 #[derive(Default, Debug)]
 pub struct AmmConfig {
@@ -26,6 +36,9 @@ impl AmmConfig {
     pub const LEN: usize = 8 + 1 + 2 + 32 + 4 + 4 + 2 + 64;
 }
 
+/// The denominator of the fee rates in `AmmConfig`, i.e. rates are in hundredths of a bip (10^-6)
+pub const FEE_RATE_DENOMINATOR_VALUE: u32 = 1_000_000;
+
 pub const POOL_SEED: &str = "pool";
 pub const POOL_VAULT_SEED: &str = "pool_vault";
 pub const POOL_REWARD_VAULT_SEED: &str = "pool_reward_vault";
@@ -61,8 +74,76 @@ pub struct RewardInfo {
     pub reward_growth_global_x64: u128,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// State of reward
+pub enum RewardState {
+    /// Reward not initialized
+    Uninitialized,
+    /// Reward initialized, but reward time is not start
+    Initialized,
+    /// Reward in progress
+    Opening,
+    /// Reward end, reward time expire or
+    Ended,
+}
+
 impl RewardInfo {
     pub const LEN: usize = 1 + 8 + 8 + 8 + 16 + 8 + 8 + 32 + 32 + 32 + 16;
+
+    /// Returns true if this reward is initialized.
+    /// Once initialized, a reward cannot transition back to uninitialized.
+    pub fn initialized(&self) -> bool {
+        self.token_mint.ne(&Pubkey::default())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolStatusBitIndex {
+    OpenPositionOrIncreaseLiquidity,
+    DecreaseLiquidity,
+    CollectFee,
+    CollectReward,
+    Swap,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolStatusBitFlag {
+    Enable,
+    Disable,
+}
+
+/// Build the `status` argument of `update_pool_status`, every operation is enabled by default
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStatusBuilder {
+    status: u8,
+}
+
+impl PoolStatusBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(mut self, bit: PoolStatusBitIndex, flag: PoolStatusBitFlag) -> Self {
+        let s = 1u8 << (bit as u8);
+        if flag == PoolStatusBitFlag::Disable {
+            self.status |= s;
+        } else {
+            self.status &= !s;
+        }
+        self
+    }
+
+    pub fn enable(self, bit: PoolStatusBitIndex) -> Self {
+        self.set(bit, PoolStatusBitFlag::Enable)
+    }
+
+    pub fn disable(self, bit: PoolStatusBitIndex) -> Self {
+        self.set(bit, PoolStatusBitFlag::Disable)
+    }
+
+    pub fn build(self) -> u8 {
+        self.status
+    }
 }
 
 #[account(zero_copy(unsafe))]
@@ -180,7 +261,7 @@ impl PoolState {
 
     pub fn seeds(&self) -> [&[u8]; 5] {
         [
-            &POOL_SEED.as_bytes(),
+            POOL_SEED.as_bytes(),
             self.amm_config.as_ref(),
             self.token_mint_0.as_ref(),
             self.token_mint_1.as_ref(),
@@ -191,6 +272,268 @@ impl PoolState {
     pub fn key(&self) -> Pubkey {
         Pubkey::create_program_address(&self.seeds(), &crate::id()).unwrap()
     }
+
+    /// Get status by bit, if it is `noraml` status, return true
+    pub fn get_status_by_bit(&self, bit: PoolStatusBitIndex) -> bool {
+        let status = 1u8 << (bit as u8);
+        self.status & status == 0
+    }
+
+    pub fn is_open_position_or_increase_liquidity_enabled(&self) -> bool {
+        self.get_status_by_bit(PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity)
+    }
+
+    pub fn is_decrease_liquidity_enabled(&self) -> bool {
+        self.get_status_by_bit(PoolStatusBitIndex::DecreaseLiquidity)
+    }
+
+    pub fn is_collect_fee_enabled(&self) -> bool {
+        self.get_status_by_bit(PoolStatusBitIndex::CollectFee)
+    }
+
+    pub fn is_collect_reward_enabled(&self) -> bool {
+        self.get_status_by_bit(PoolStatusBitIndex::CollectReward)
+    }
+
+    /// The swap is also rejected before `open_time`, which is not checked here
+    pub fn is_swap_enabled(&self) -> bool {
+        self.get_status_by_bit(PoolStatusBitIndex::Swap)
+    }
+
+    /// Search the first initialized tick array from the current tick in the swap direction,
+    /// the first element of the result is true if the tick array containing `tick_current` is initialized.
+    pub fn get_first_initialized_tick_array(
+        &self,
+        tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
+        zero_for_one: bool,
+    ) -> Result<(bool, i32)> {
+        let (is_initialized, start_index) =
+            if self.is_overflow_default_tickarray_bitmap(vec![self.tick_current]) {
+                tickarray_bitmap_extension
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingTickArrayBitmapExtensionAccount)?
+                    .check_tick_array_is_initialized(
+                        TickArrayState::get_array_start_index(self.tick_current, self.tick_spacing),
+                        self.tick_spacing,
+                    )?
+            } else {
+                tick_array_bit_map::check_current_tick_array_is_initialized(
+                    U1024(self.tick_array_bitmap),
+                    self.tick_current,
+                    self.tick_spacing,
+                )?
+            };
+        if is_initialized {
+            return Ok((true, start_index));
+        }
+        let next_start_index = self.next_initialized_tick_array_start_index(
+            tickarray_bitmap_extension,
+            TickArrayState::get_array_start_index(self.tick_current, self.tick_spacing),
+            zero_for_one,
+        )?;
+        require!(
+            next_start_index.is_some(),
+            ErrorCode::InsufficientLiquidityForDirection
+        );
+        Ok((false, next_start_index.unwrap()))
+    }
+
+    /// The first `max_count` initialized tick arrays a swap in the direction may walk through, in order.
+    /// Without `tickarray_bitmap_extension` the walk stops at the range of the default bitmap,
+    /// the extension is then reported as needed if the swap may go further.
+    /// `SwapResult::tick_arrays` gives the tick arrays of a quoted swap instead.
+    pub fn get_swap_tick_arrays(
+        &self,
+        tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
+        zero_for_one: bool,
+        max_count: usize,
+    ) -> Result<SwapTickArrays> {
+        let mut start_indexes = Vec::new();
+        if max_count == 0 {
+            return Ok(self.swap_tick_arrays(start_indexes));
+        }
+        let (_, mut start_index) =
+            self.get_first_initialized_tick_array(tickarray_bitmap_extension, zero_for_one)?;
+        start_indexes.push(start_index);
+        while start_indexes.len() < max_count {
+            match self.next_initialized_tick_array_start_index(
+                tickarray_bitmap_extension,
+                start_index,
+                zero_for_one,
+            ) {
+                Ok(Some(next_start_index)) => {
+                    start_index = next_start_index;
+                    start_indexes.push(start_index);
+                }
+                Ok(None) => break,
+                Err(error) if error == ErrorCode::MissingTickArrayBitmapExtensionAccount.into() => {
+                    return Ok(SwapTickArrays {
+                        start_indexes,
+                        is_bitmap_extension_needed: true,
+                    });
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(self.swap_tick_arrays(start_indexes))
+    }
+
+    /// The tick arrays of `start_indexes`, the bitmap extension is needed when one of them
+    /// is out of the range of the default bitmap.
+    pub fn swap_tick_arrays(&self, start_indexes: Vec<i32>) -> SwapTickArrays {
+        SwapTickArrays {
+            is_bitmap_extension_needed: self
+                .is_overflow_default_tickarray_bitmap(start_indexes.clone()),
+            start_indexes,
+        }
+    }
+
+    /// Search the next initialized tick array start index in the swap direction across
+    /// both the default bitmap and the bitmap extension.
+    ///
+    /// The extension is only required once the search goes beyond the range of the default bitmap,
+    /// returns `None` if no initialized tick array is found until the tick boundary.
+    pub fn next_initialized_tick_array_start_index(
+        &self,
+        tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
+        mut last_tick_array_start_index: i32,
+        zero_for_one: bool,
+    ) -> Result<Option<i32>> {
+        last_tick_array_start_index =
+            TickArrayState::get_array_start_index(last_tick_array_start_index, self.tick_spacing);
+
+        loop {
+            let (is_found, start_index) =
+                tick_array_bit_map::next_initialized_tick_array_start_index(
+                    U1024(self.tick_array_bitmap),
+                    last_tick_array_start_index,
+                    self.tick_spacing,
+                    zero_for_one,
+                );
+            if is_found {
+                return Ok(Some(start_index));
+            }
+            last_tick_array_start_index = start_index;
+
+            let (is_found, start_index) = tickarray_bitmap_extension
+                .as_ref()
+                .ok_or(ErrorCode::MissingTickArrayBitmapExtensionAccount)?
+                .next_initialized_tick_array_from_one_bitmap(
+                    last_tick_array_start_index,
+                    self.tick_spacing,
+                    zero_for_one,
+                )?;
+            if is_found {
+                return Ok(Some(start_index));
+            }
+            last_tick_array_start_index = start_index;
+
+            if !(tick_math::MIN_TICK..=tick_math::MAX_TICK).contains(&last_tick_array_start_index) {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Whether any of the tick arrays containing `tick_indexs` is out of the range
+    /// the default `tick_array_bitmap` can represent, which means the bitmap extension is needed.
+    pub fn is_overflow_default_tickarray_bitmap(&self, tick_indexs: Vec<i32>) -> bool {
+        let (min_tick_array_start_index_boundary, max_tick_array_index_boundary) =
+            self.tick_array_start_index_range();
+        for tick_index in tick_indexs {
+            let tick_array_start_index =
+                TickArrayState::get_array_start_index(tick_index, self.tick_spacing);
+            if tick_array_start_index >= max_tick_array_index_boundary
+                || tick_array_start_index < min_tick_array_start_index_boundary
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The range of tick array start index that default tickarray bitmap can represent,
+    /// if tick_spacing = 1, the result range is [-30720, 30720)
+    pub fn tick_array_start_index_range(&self) -> (i32, i32) {
+        // the range of ticks that default tickarrary can represent
+        let mut max_tick_boundary =
+            tick_array_bit_map::max_tick_in_tickarray_bitmap(self.tick_spacing);
+        let mut min_tick_boundary = -max_tick_boundary;
+        if max_tick_boundary > tick_math::MAX_TICK {
+            max_tick_boundary =
+                TickArrayState::get_array_start_index(tick_math::MAX_TICK, self.tick_spacing);
+            // find the next tick array start index
+            max_tick_boundary += TickArrayState::tick_count(self.tick_spacing);
+        }
+        if min_tick_boundary < tick_math::MIN_TICK {
+            min_tick_boundary =
+                TickArrayState::get_array_start_index(tick_math::MIN_TICK, self.tick_spacing);
+        }
+        (min_tick_boundary, max_tick_boundary)
+    }
+
+    /// The reward infos of the pool at `curr_timestamp`, as the `update_reward_infos` instruction would
+    /// write them. The pool state is left unchanged.
+    pub fn get_latest_reward_infos(&self, curr_timestamp: u64) -> Result<[RewardInfo; REWARD_NUM]> {
+        let mut next_reward_infos = self.reward_infos;
+
+        for reward_info in next_reward_infos.iter_mut() {
+            if !reward_info.initialized() {
+                continue;
+            }
+            if curr_timestamp <= reward_info.open_time {
+                continue;
+            }
+            let latest_update_timestamp = curr_timestamp.min(reward_info.end_time);
+
+            if self.liquidity != 0 {
+                require_gte!(latest_update_timestamp, reward_info.last_update_time);
+                let time_delta = latest_update_timestamp - reward_info.last_update_time;
+
+                let reward_growth_delta = U256::from(time_delta)
+                    .mul_div_floor(
+                        U256::from(reward_info.emissions_per_second_x64),
+                        U256::from(self.liquidity),
+                    )
+                    .ok_or(ErrorCode::CalculateOverflow)?;
+
+                reward_info.reward_growth_global_x64 = reward_info
+                    .reward_growth_global_x64
+                    .checked_add(reward_growth_delta.as_u128())
+                    .ok_or(ErrorCode::CalculateOverflow)?;
+
+                let reward_emissioned = U128::from(time_delta)
+                    .mul_div_ceil(
+                        U128::from(reward_info.emissions_per_second_x64),
+                        U128::from(fixed_point_64::Q64),
+                    )
+                    .ok_or(ErrorCode::CalculateOverflow)?
+                    .as_u64();
+                reward_info.reward_total_emissioned = reward_info
+                    .reward_total_emissioned
+                    .checked_add(reward_emissioned)
+                    .ok_or(ErrorCode::CalculateOverflow)?;
+            }
+            reward_info.last_update_time = latest_update_timestamp;
+            // update reward state
+            if latest_update_timestamp >= reward_info.open_time
+                && latest_update_timestamp < reward_info.end_time
+            {
+                reward_info.reward_state = RewardState::Opening as u8;
+            } else if latest_update_timestamp == reward_info.end_time {
+                reward_info.reward_state = RewardState::Ended as u8;
+            }
+        }
+        Ok(next_reward_infos)
+    }
+}
+
+/// The tick arrays a swap walks through, see `PoolState::get_swap_tick_arrays`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapTickArrays {
+    /// Start indexes of the tick arrays, in the order the swap instructions expect them
+    pub start_indexes: Vec<i32>,
+    /// Whether the bitmap extension account must be passed before the tick arrays
+    pub is_bitmap_extension_needed: bool,
 }
 
 pub const OPERATION_SEED: &str = "operation";
@@ -209,6 +552,34 @@ pub struct OperationState {
     pub whitelist_mints: [Pubkey; WHITE_MINT_SIZE_USIZE],
 }
 
+impl OperationState {
+    pub const LEN: usize = 8 + 1 + 32 * OPERATION_SIZE_USIZE + 32 * WHITE_MINT_SIZE_USIZE;
+
+    /// Whether `mint` is in the whitelist of reward mints
+    pub fn validate_whitelist_mint(&self, mint: &Pubkey) -> bool {
+        let whitelist_mints = self.whitelist_mints;
+        *mint != Pubkey::default() && whitelist_mints.contains(mint)
+    }
+}
+
+pub const SUPPORT_MINT_SEED: &str = "support_mint";
+
+/// Created by the admin for a Token-2022 mint with extensions not supported by default,
+/// allowing the mint to create pools and be used as reward
+#[account]
+#[derive(Default, Debug)]
+pub struct SupportMintAssociated {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The supported mint
+    pub mint: Pubkey,
+    pub padding: [u64; 8],
+}
+
+impl SupportMintAssociated {
+    pub const LEN: usize = 8 + 1 + 32 + 8 * 8;
+}
+
 /// Seed to derive account address and signature
 pub const OBSERVATION_SEED: &str = "observation";
 // Number of ObservationState element
@@ -265,4 +636,795 @@ impl Default for ObservationState {
 
 impl ObservationState {
     pub const LEN: usize = 8 + 1 + 8 + 2 + 32 + (Observation::LEN * OBSERVATION_NUM) + 8 * 4;
-}
\ No newline at end of file
+
+    /// The observations written so far, from the oldest to the most recent
+    pub fn observations_in_order(&self) -> Vec<Observation> {
+        if !self.initialized {
+            return Vec::new();
+        }
+        let observations = self.observations;
+        let index = self.observation_index as usize;
+        let next_index = (index + 1) % OBSERVATION_NUM;
+        // the ring buffer is not full yet
+        let mut i = if observations[next_index].block_timestamp == 0 {
+            0
+        } else {
+            next_index
+        };
+        let mut ordered = Vec::with_capacity(OBSERVATION_NUM);
+        loop {
+            ordered.push(observations[i]);
+            if i == index {
+                return ordered;
+            }
+            i = (i + 1) % OBSERVATION_NUM;
+        }
+    }
+
+    /// The longest window in seconds before `block_timestamp` the oracle can look back
+    pub fn oldest_window(&self, block_timestamp: u32) -> Option<u32> {
+        let oldest = *self.observations_in_order().first()?;
+        Some(block_timestamp.wrapping_sub(oldest.block_timestamp))
+    }
+
+    /// The tick cumulative at `seconds_ago` before `block_timestamp`.
+    ///
+    /// It is interpolated between the two observations around the target time, or extrapolated
+    /// from the most recent observation with `tick_current`, the current tick of the pool.
+    /// Returns `None` if the target time is older than the oldest observation.
+    pub fn get_tick_cumulative(
+        &self,
+        block_timestamp: u32,
+        tick_current: i32,
+        seconds_ago: u32,
+    ) -> Option<i64> {
+        let observations = self.observations_in_order();
+        // compare the ages relative to block_timestamp to handle the wraparound of u32 timestamps
+        let age =
+            |observation: &Observation| block_timestamp.wrapping_sub(observation.block_timestamp);
+
+        let latest = *observations.last()?;
+        let latest_age = age(&latest);
+        if seconds_ago <= latest_age {
+            let delta_time = i64::from(latest_age - seconds_ago);
+            return Some(
+                latest
+                    .tick_cumulative
+                    .wrapping_add(i64::from(tick_current) * delta_time),
+            );
+        }
+        for pair in observations.windows(2).rev() {
+            let (before, after) = (pair[0], pair[1]);
+            let before_age = age(&before);
+            if seconds_ago <= before_age {
+                // observations out of order or at the same time can not be interpolated
+                let duration = before_age
+                    .checked_sub(age(&after))
+                    .filter(|duration| *duration > 0)?;
+                let delta_time = before_age - seconds_ago;
+                let delta = i128::from(after.tick_cumulative.wrapping_sub(before.tick_cumulative))
+                    * i128::from(delta_time)
+                    / i128::from(duration);
+                return Some(before.tick_cumulative.wrapping_add(delta as i64));
+            }
+        }
+        None
+    }
+
+    /// The time-weighted average tick between `seconds_ago_start` and `seconds_ago_end` before `block_timestamp`,
+    /// rounded towards negative infinity.
+    pub fn get_twap_tick(
+        &self,
+        block_timestamp: u32,
+        tick_current: i32,
+        seconds_ago_start: u32,
+        seconds_ago_end: u32,
+    ) -> Option<i32> {
+        if seconds_ago_start <= seconds_ago_end {
+            return None;
+        }
+        let tick_cumulative_start =
+            self.get_tick_cumulative(block_timestamp, tick_current, seconds_ago_start)?;
+        let tick_cumulative_end =
+            self.get_tick_cumulative(block_timestamp, tick_current, seconds_ago_end)?;
+        let duration = i64::from(seconds_ago_start - seconds_ago_end);
+        i32::try_from(
+            tick_cumulative_end
+                .wrapping_sub(tick_cumulative_start)
+                .div_euclid(duration),
+        )
+        .ok()
+    }
+
+    /// The sqrt price as a Q64.64 at the time-weighted average tick, see `get_twap_tick`
+    pub fn get_twap_sqrt_price_x64(
+        &self,
+        block_timestamp: u32,
+        tick_current: i32,
+        seconds_ago_start: u32,
+        seconds_ago_end: u32,
+    ) -> Option<u128> {
+        let tick = self.get_twap_tick(
+            block_timestamp,
+            tick_current,
+            seconds_ago_start,
+            seconds_ago_end,
+        )?;
+        tick_math::get_sqrt_price_at_tick(tick).ok()
+    }
+}
+
+pub const POSITION_SEED: &str = "position";
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct PositionRewardInfo {
+    // Q64.64
+    pub growth_inside_last_x64: u128,
+    pub reward_amount_owed: u64,
+}
+
+impl PositionRewardInfo {
+    pub const LEN: usize = 16 + 8;
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct PersonalPositionState {
+    /// Bump to identify PDA
+    pub bump: u8,
+
+    /// Mint address of the tokenized position
+    pub nft_mint: Pubkey,
+
+    /// The ID of the pool with which this token is connected
+    pub pool_id: Pubkey,
+
+    /// The lower bound tick of the position
+    pub tick_lower_index: i32,
+
+    /// The upper bound tick of the position
+    pub tick_upper_index: i32,
+
+    /// The amount of liquidity owned by this position
+    pub liquidity: u128,
+
+    /// The token_0 fee growth of the aggregate position as of the last action on the individual position
+    pub fee_growth_inside_0_last_x64: u128,
+
+    /// The token_1 fee growth of the aggregate position as of the last action on the individual position
+    pub fee_growth_inside_1_last_x64: u128,
+
+    /// The fees owed to the position owner in token_0, as of the last computation
+    pub token_fees_owed_0: u64,
+
+    /// The fees owed to the position owner in token_1, as of the last computation
+    pub token_fees_owed_1: u64,
+
+    // Position reward info
+    pub reward_infos: [PositionRewardInfo; REWARD_NUM],
+    // account update recent epoch
+    pub recent_epoch: u64,
+    // Unused bytes for future upgrades.
+    pub padding: [u64; 7],
+}
+
+impl PersonalPositionState {
+    pub const LEN: usize = 8
+        + 1
+        + 32
+        + 32
+        + 4
+        + 4
+        + 16
+        + 16
+        + 16
+        + 8
+        + 8
+        + PositionRewardInfo::LEN * REWARD_NUM
+        + 8
+        + 8 * 7;
+
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [
+            POSITION_SEED.as_bytes(),
+            self.nft_mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+
+    pub fn key(&self) -> Pubkey {
+        Pubkey::create_program_address(&self.seeds(), &crate::id()).unwrap()
+    }
+
+    /// The fees owed to the position, as collected by a `decrease_liquidity` with zero liquidity.
+    /// `tick_lower` and `tick_upper` are the tick states of the position's boundaries.
+    pub fn get_pending_fees(
+        &self,
+        pool_state: &PoolState,
+        tick_lower: &TickState,
+        tick_upper: &TickState,
+    ) -> Result<(u64, u64)> {
+        require!(
+            tick_lower.tick == self.tick_lower_index && tick_upper.tick == self.tick_upper_index,
+            ErrorCode::InvaildTickIndex
+        );
+        let (fee_growth_inside_0_x64, fee_growth_inside_1_x64) = get_fee_growth_inside(
+            tick_lower,
+            tick_upper,
+            pool_state.tick_current,
+            pool_state.fee_growth_global_0_x64,
+            pool_state.fee_growth_global_1_x64,
+        );
+        let token_fees_owed_0 = calculate_latest_token_fees(
+            self.token_fees_owed_0,
+            self.fee_growth_inside_0_last_x64,
+            fee_growth_inside_0_x64,
+            self.liquidity,
+        )?;
+        let token_fees_owed_1 = calculate_latest_token_fees(
+            self.token_fees_owed_1,
+            self.fee_growth_inside_1_last_x64,
+            fee_growth_inside_1_x64,
+            self.liquidity,
+        )?;
+        Ok((token_fees_owed_0, token_fees_owed_1))
+    }
+
+    /// The rewards owed to the position at `curr_timestamp`, as collected by a `decrease_liquidity` with zero liquidity.
+    /// `tick_lower` and `tick_upper` are the tick states of the position's boundaries.
+    pub fn get_pending_rewards(
+        &self,
+        pool_state: &PoolState,
+        tick_lower: &TickState,
+        tick_upper: &TickState,
+        curr_timestamp: u64,
+    ) -> Result<[u64; REWARD_NUM]> {
+        require!(
+            tick_lower.tick == self.tick_lower_index && tick_upper.tick == self.tick_upper_index,
+            ErrorCode::InvaildTickIndex
+        );
+        let reward_infos = pool_state.get_latest_reward_infos(curr_timestamp)?;
+        let reward_growths_inside = get_reward_growths_inside(
+            tick_lower,
+            tick_upper,
+            pool_state.tick_current,
+            &reward_infos,
+        );
+
+        let mut reward_amounts = [0u64; REWARD_NUM];
+        for i in 0..REWARD_NUM {
+            let curr_reward_info = self.reward_infos[i];
+            let reward_growth_delta =
+                reward_growths_inside[i].wrapping_sub(curr_reward_info.growth_inside_last_x64);
+            let amount_owed_delta = U256::from(reward_growth_delta)
+                .mul_div_floor(U256::from(self.liquidity), U256::from(fixed_point_64::Q64))
+                .ok_or(ErrorCode::CalculateOverflow)?
+                .to_underflow_u64();
+            reward_amounts[i] = curr_reward_info
+                .reward_amount_owed
+                .checked_add(amount_owed_delta)
+                .ok_or(ErrorCode::CalculateOverflow)?;
+        }
+        Ok(reward_amounts)
+    }
+}
+
+/// Add the fees earned by `liquidity` since `fee_growth_inside_last_x64` to `last_total_fees`
+pub fn calculate_latest_token_fees(
+    last_total_fees: u64,
+    fee_growth_inside_last_x64: u128,
+    fee_growth_inside_latest_x64: u128,
+    liquidity: u128,
+) -> Result<u64> {
+    let fee_growth_delta =
+        U128::from(fee_growth_inside_latest_x64.wrapping_sub(fee_growth_inside_last_x64))
+            .mul_div_floor(U128::from(liquidity), U128::from(fixed_point_64::Q64))
+            .ok_or(ErrorCode::CalculateOverflow)?
+            .to_underflow_u64();
+    Ok(last_total_fees
+        .checked_add(fee_growth_delta)
+        .ok_or(ErrorCode::CalculateOverflow)?)
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct ProtocolPositionState {
+    /// Bump to identify PDA
+    pub bump: u8,
+
+    /// The ID of the pool with which this token is connected
+    pub pool_id: Pubkey,
+
+    /// The lower bound tick of the position
+    pub tick_lower_index: i32,
+
+    /// The upper bound tick of the position
+    pub tick_upper_index: i32,
+
+    /// The amount of liquidity owned by this position
+    pub liquidity: u128,
+
+    /// The token_0 fee growth per unit of liquidity as of the last update to liquidity or fees owed
+    pub fee_growth_inside_0_last_x64: u128,
+
+    /// The token_1 fee growth per unit of liquidity as of the last update to liquidity or fees owed
+    pub fee_growth_inside_1_last_x64: u128,
+
+    /// The fees owed to the position owner in token_0
+    pub token_fees_owed_0: u64,
+
+    /// The fees owed to the position owner in token_1
+    pub token_fees_owed_1: u64,
+
+    /// The reward growth per unit of liquidity as of the last update to liquidity
+    pub reward_growth_inside: [u128; REWARD_NUM],
+    // account update recent epoch
+    pub recent_epoch: u64,
+    // Unused bytes for future upgrades.
+    pub padding: [u64; 7],
+}
+
+impl ProtocolPositionState {
+    pub const LEN: usize = 8 + 1 + 32 + 4 + 4 + 16 + 16 + 16 + 8 + 8 + 16 * REWARD_NUM + 8 + 8 * 7;
+
+    /// Derive the address of the protocol position aggregating all personal positions
+    /// of `pool_id` within `[tick_lower_index, tick_upper_index)`
+    pub fn find_address(
+        pool_id: &Pubkey,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> (Pubkey, u8) {
+        pda::find_protocol_position_address(pool_id, tick_lower_index, tick_upper_index)
+    }
+
+    pub fn key(&self) -> Pubkey {
+        Pubkey::create_program_address(
+            &[
+                POSITION_SEED.as_bytes(),
+                self.pool_id.as_ref(),
+                &self.tick_lower_index.to_be_bytes(),
+                &self.tick_upper_index.to_be_bytes(),
+                &[self.bump],
+            ],
+            &crate::id(),
+        )
+        .unwrap()
+    }
+}
+
+pub const TICK_ARRAY_SEED: &str = "tick_array";
+pub const TICK_ARRAY_SIZE_USIZE: usize = 60;
+pub const TICK_ARRAY_SIZE: i32 = 60;
+
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+pub struct TickArrayState {
+    pub pool_id: Pubkey,
+    pub start_tick_index: i32,
+    pub ticks: [TickState; TICK_ARRAY_SIZE_USIZE],
+    pub initialized_tick_count: u8,
+    // account update recent epoch
+    pub recent_epoch: u64,
+    // Unused bytes for future upgrades.
+    pub padding: [u8; 107],
+}
+
+impl Default for TickArrayState {
+    #[inline]
+    fn default() -> TickArrayState {
+        TickArrayState {
+            pool_id: Pubkey::default(),
+            start_tick_index: 0,
+            ticks: [TickState::default(); TICK_ARRAY_SIZE_USIZE],
+            initialized_tick_count: 0,
+            recent_epoch: 0,
+            padding: [0; 107],
+        }
+    }
+}
+
+impl TickArrayState {
+    pub const LEN: usize = 8 + 32 + 4 + TickState::LEN * TICK_ARRAY_SIZE_USIZE + 1 + 8 + 107;
+
+    pub fn key(&self) -> Pubkey {
+        pda::find_tick_array_address(&self.pool_id, self.start_tick_index).0
+    }
+
+    /// Get the tick state of `tick_index`, the tick must be included in this tick array
+    pub fn get_tick_state(&self, tick_index: i32, tick_spacing: u16) -> Result<&TickState> {
+        let offset_in_array = self.get_tick_offset_in_array(tick_index, tick_spacing)?;
+        Ok(&self.ticks[offset_in_array])
+    }
+
+    pub fn get_tick_state_mut(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+    ) -> Result<&mut TickState> {
+        let offset_in_array = self.get_tick_offset_in_array(tick_index, tick_spacing)?;
+        Ok(&mut self.ticks[offset_in_array])
+    }
+
+    /// Get tick's offset in current tick array, tick must be include in tick array, otherwise throw an error
+    pub fn get_tick_offset_in_array(&self, tick_index: i32, tick_spacing: u16) -> Result<usize> {
+        let start_tick_index = TickArrayState::get_array_start_index(tick_index, tick_spacing);
+        require_eq!(
+            start_tick_index,
+            self.start_tick_index,
+            ErrorCode::InvalidTickArray
+        );
+        let offset_in_array =
+            ((tick_index - self.start_tick_index) / i32::from(tick_spacing)) as usize;
+        Ok(offset_in_array)
+    }
+
+    /// Base on swap directioin, return the first initialized tick in the tick array.
+    pub fn first_initialized_tick(&self, zero_for_one: bool) -> Result<&TickState> {
+        let tick_state = if zero_for_one {
+            self.ticks.iter().rev().find(|tick| tick.is_initialized())
+        } else {
+            self.ticks.iter().find(|tick| tick.is_initialized())
+        };
+        tick_state.ok_or_else(|| ErrorCode::InvalidTickArray.into())
+    }
+
+    /// Get next initialized tick in tick array, `current_tick_index` can be any tick index, in other words, `current_tick_index` not exactly a point in the tickarray,
+    /// and current_tick_index % tick_spacing maybe not equal zero.
+    /// If price move to left tick <= current_tick_index, or to right tick > current_tick_index
+    pub fn next_initialized_tick(
+        &self,
+        current_tick_index: i32,
+        tick_spacing: u16,
+        zero_for_one: bool,
+    ) -> Option<&TickState> {
+        let current_tick_array_start_index =
+            TickArrayState::get_array_start_index(current_tick_index, tick_spacing);
+        if current_tick_array_start_index != self.start_tick_index {
+            return None;
+        }
+        let offset_in_array =
+            ((current_tick_index - self.start_tick_index) / i32::from(tick_spacing)) as usize;
+
+        if zero_for_one {
+            self.ticks[..=offset_in_array]
+                .iter()
+                .rev()
+                .find(|tick| tick.is_initialized())
+        } else {
+            self.ticks[offset_in_array + 1..]
+                .iter()
+                .find(|tick| tick.is_initialized())
+        }
+    }
+
+    /// Base on swap directioin, return the next tick array start index.
+    pub fn get_next_tick_array_start_index(&self, tick_spacing: u16, zero_for_one: bool) -> i32 {
+        let ticks_in_array = TickArrayState::tick_count(tick_spacing);
+        if zero_for_one {
+            self.start_tick_index - ticks_in_array
+        } else {
+            self.start_tick_index + ticks_in_array
+        }
+    }
+
+    /// Input an arbitrary tick_index, output the start_index of the tick_array it sits on
+    pub fn get_array_start_index(tick_index: i32, tick_spacing: u16) -> i32 {
+        let ticks_in_array = TickArrayState::tick_count(tick_spacing);
+        let mut start = tick_index / ticks_in_array;
+        if tick_index < 0 && tick_index % ticks_in_array != 0 {
+            start -= 1
+        }
+        start * ticks_in_array
+    }
+
+    pub fn check_is_valid_start_index(tick_index: i32, tick_spacing: u16) -> bool {
+        if TickState::check_is_out_of_boundary(tick_index) {
+            if tick_index > tick_math::MAX_TICK {
+                return false;
+            }
+            let min_start_index =
+                TickArrayState::get_array_start_index(tick_math::MIN_TICK, tick_spacing);
+            return tick_index == min_start_index;
+        }
+        tick_index % TickArrayState::tick_count(tick_spacing) == 0
+    }
+
+    /// The number of ticks covered by one tick array
+    pub fn tick_count(tick_spacing: u16) -> i32 {
+        TICK_ARRAY_SIZE * i32::from(tick_spacing)
+    }
+}
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct TickState {
+    pub tick: i32,
+    /// Amount of net liquidity added (subtracted) when tick is crossed from left to right (right to left)
+    pub liquidity_net: i128,
+    /// The total position liquidity that references this tick
+    pub liquidity_gross: u128,
+
+    /// Fee growth per unit of liquidity on the _other_ side of this tick (relative to the current tick)
+    /// only has relative meaning, not absolute — the value depends on when the tick is initialized
+    pub fee_growth_outside_0_x64: u128,
+    pub fee_growth_outside_1_x64: u128,
+
+    // Reward growth per unit of liquidity like fee, array of Q64.64
+    pub reward_growths_outside_x64: [u128; REWARD_NUM],
+    // Unused bytes for future upgrades.
+    pub padding: [u32; 13],
+}
+
+impl TickState {
+    pub const LEN: usize = 4 + 16 + 16 + 16 + 16 + 16 * REWARD_NUM + 4 * 13;
+
+    pub fn is_initialized(&self) -> bool {
+        self.liquidity_gross != 0
+    }
+
+    /// Common checks for a valid tick input.
+    /// A tick is valid if it lies within tick boundaries
+    pub fn check_is_out_of_boundary(tick: i32) -> bool {
+        !(tick_math::MIN_TICK..=tick_math::MAX_TICK).contains(&tick)
+    }
+}
+
+/// Calculates the fee growths inside of tick_lower and tick_upper based on their positions relative to tick current.
+/// The growths wrap around as on chain, only the difference between two values is meaningful.
+pub fn get_fee_growth_inside(
+    tick_lower: &TickState,
+    tick_upper: &TickState,
+    tick_current: i32,
+    fee_growth_global_0_x64: u128,
+    fee_growth_global_1_x64: u128,
+) -> (u128, u128) {
+    // calculate fee growth below
+    let (fee_growth_below_0_x64, fee_growth_below_1_x64) = if tick_current >= tick_lower.tick {
+        (
+            tick_lower.fee_growth_outside_0_x64,
+            tick_lower.fee_growth_outside_1_x64,
+        )
+    } else {
+        (
+            fee_growth_global_0_x64.wrapping_sub(tick_lower.fee_growth_outside_0_x64),
+            fee_growth_global_1_x64.wrapping_sub(tick_lower.fee_growth_outside_1_x64),
+        )
+    };
+
+    // Calculate fee growth above
+    let (fee_growth_above_0_x64, fee_growth_above_1_x64) = if tick_current < tick_upper.tick {
+        (
+            tick_upper.fee_growth_outside_0_x64,
+            tick_upper.fee_growth_outside_1_x64,
+        )
+    } else {
+        (
+            fee_growth_global_0_x64.wrapping_sub(tick_upper.fee_growth_outside_0_x64),
+            fee_growth_global_1_x64.wrapping_sub(tick_upper.fee_growth_outside_1_x64),
+        )
+    };
+    let fee_growth_inside_0_x64 = fee_growth_global_0_x64
+        .wrapping_sub(fee_growth_below_0_x64)
+        .wrapping_sub(fee_growth_above_0_x64);
+    let fee_growth_inside_1_x64 = fee_growth_global_1_x64
+        .wrapping_sub(fee_growth_below_1_x64)
+        .wrapping_sub(fee_growth_above_1_x64);
+
+    (fee_growth_inside_0_x64, fee_growth_inside_1_x64)
+}
+
+/// Calculates the reward growths inside of tick_lower and tick_upper based on their positions relative to tick current.
+pub fn get_reward_growths_inside(
+    tick_lower: &TickState,
+    tick_upper: &TickState,
+    tick_current_index: i32,
+    reward_infos: &[RewardInfo; REWARD_NUM],
+) -> [u128; REWARD_NUM] {
+    let mut reward_growths_inside = [0; REWARD_NUM];
+
+    for i in 0..REWARD_NUM {
+        if !reward_infos[i].initialized() {
+            continue;
+        }
+        let reward_growth_global_x64 = reward_infos[i].reward_growth_global_x64;
+
+        let reward_growths_below = if tick_current_index >= tick_lower.tick {
+            tick_lower.reward_growths_outside_x64[i]
+        } else {
+            reward_growth_global_x64.wrapping_sub(tick_lower.reward_growths_outside_x64[i])
+        };
+
+        let reward_growths_above = if tick_current_index < tick_upper.tick {
+            tick_upper.reward_growths_outside_x64[i]
+        } else {
+            reward_growth_global_x64.wrapping_sub(tick_upper.reward_growths_outside_x64[i])
+        };
+        reward_growths_inside[i] = reward_growth_global_x64
+            .wrapping_sub(reward_growths_below)
+            .wrapping_sub(reward_growths_above);
+    }
+
+    reward_growths_inside
+}
+
+const EXTENSION_TICKARRAY_BITMAP_SIZE: usize = 14;
+
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Debug)]
+pub struct TickArrayBitmapExtension {
+    pub pool_id: Pubkey,
+    /// Packed initialized tick array state for start_tick_index is positive
+    pub positive_tick_array_bitmap: [[u64; 8]; EXTENSION_TICKARRAY_BITMAP_SIZE],
+    /// Packed initialized tick array state for start_tick_index is negitive
+    pub negative_tick_array_bitmap: [[u64; 8]; EXTENSION_TICKARRAY_BITMAP_SIZE],
+}
+
+impl Default for TickArrayBitmapExtension {
+    #[inline]
+    fn default() -> TickArrayBitmapExtension {
+        TickArrayBitmapExtension {
+            pool_id: Pubkey::default(),
+            positive_tick_array_bitmap: [[0; 8]; EXTENSION_TICKARRAY_BITMAP_SIZE],
+            negative_tick_array_bitmap: [[0; 8]; EXTENSION_TICKARRAY_BITMAP_SIZE],
+        }
+    }
+}
+
+impl TickArrayBitmapExtension {
+    pub const LEN: usize = 8 + 32 + 64 * EXTENSION_TICKARRAY_BITMAP_SIZE * 2;
+
+    pub fn key(pool_id: Pubkey) -> Pubkey {
+        pda::find_bitmap_extension_address(&pool_id).0
+    }
+
+    fn get_bitmap_offset(tick_index: i32, tick_spacing: u16) -> Result<usize> {
+        require!(
+            TickArrayState::check_is_valid_start_index(tick_index, tick_spacing),
+            ErrorCode::InvaildTickIndex
+        );
+        Self::check_extension_boundary(tick_index, tick_spacing)?;
+        let ticks_in_one_bitmap = tick_array_bit_map::max_tick_in_tickarray_bitmap(tick_spacing);
+        let mut offset = tick_index.abs() / ticks_in_one_bitmap - 1;
+        if tick_index < 0 && tick_index.abs() % ticks_in_one_bitmap == 0 {
+            offset -= 1;
+        }
+        Ok(offset as usize)
+    }
+
+    /// According to the given tick, calculate its corresponding tickarray and then find the bitmap it belongs to.
+    fn get_bitmap(&self, tick_index: i32, tick_spacing: u16) -> Result<(usize, TickArryBitmap)> {
+        let offset = Self::get_bitmap_offset(tick_index, tick_spacing)?;
+        if tick_index < 0 {
+            Ok((offset, self.negative_tick_array_bitmap[offset]))
+        } else {
+            Ok((offset, self.positive_tick_array_bitmap[offset]))
+        }
+    }
+
+    /// Check if the tick in tick array bitmap extension
+    pub fn check_extension_boundary(tick_index: i32, tick_spacing: u16) -> Result<()> {
+        let positive_tick_boundary = tick_array_bit_map::max_tick_in_tickarray_bitmap(tick_spacing);
+        let negative_tick_boundary = -positive_tick_boundary;
+        require_gt!(tick_math::MAX_TICK, positive_tick_boundary);
+        require_gt!(negative_tick_boundary, tick_math::MIN_TICK);
+        if tick_index >= negative_tick_boundary && tick_index < positive_tick_boundary {
+            return err!(ErrorCode::InvalidTickArrayBoundary);
+        }
+        Ok(())
+    }
+
+    /// Check if the tick array is initialized
+    pub fn check_tick_array_is_initialized(
+        &self,
+        tick_array_start_index: i32,
+        tick_spacing: u16,
+    ) -> Result<(bool, i32)> {
+        let (_, tickarray_bitmap) = self.get_bitmap(tick_array_start_index, tick_spacing)?;
+
+        let tick_array_offset_in_bitmap =
+            Self::tick_array_offset_in_bitmap(tick_array_start_index, tick_spacing);
+
+        Ok((
+            U512(tickarray_bitmap).bit(tick_array_offset_in_bitmap as usize),
+            tick_array_start_index,
+        ))
+    }
+
+    /// Search for the first initialized bit in bitmap according to the direction, if found return ture and the tick array start index,
+    /// if not, return false and tick boundary index
+    pub fn next_initialized_tick_array_from_one_bitmap(
+        &self,
+        last_tick_array_start_index: i32,
+        tick_spacing: u16,
+        zero_for_one: bool,
+    ) -> Result<(bool, i32)> {
+        let multiplier = TickArrayState::tick_count(tick_spacing);
+        let next_tick_array_start_index = if zero_for_one {
+            last_tick_array_start_index - multiplier
+        } else {
+            last_tick_array_start_index + multiplier
+        };
+        let min_tick_array_start_index =
+            TickArrayState::get_array_start_index(tick_math::MIN_TICK, tick_spacing);
+        let max_tick_array_start_index =
+            TickArrayState::get_array_start_index(tick_math::MAX_TICK, tick_spacing);
+
+        if next_tick_array_start_index < min_tick_array_start_index
+            || next_tick_array_start_index > max_tick_array_start_index
+        {
+            return Ok((false, next_tick_array_start_index));
+        }
+
+        let (_, tickarray_bitmap) = self.get_bitmap(next_tick_array_start_index, tick_spacing)?;
+
+        Ok(Self::next_initialized_tick_array_in_bitmap(
+            tickarray_bitmap,
+            next_tick_array_start_index,
+            tick_spacing,
+            zero_for_one,
+        ))
+    }
+
+    pub fn next_initialized_tick_array_in_bitmap(
+        tickarray_bitmap: TickArryBitmap,
+        next_tick_array_start_index: i32,
+        tick_spacing: u16,
+        zero_for_one: bool,
+    ) -> (bool, i32) {
+        let (bitmap_min_tick_boundary, bitmap_max_tick_boundary) =
+            tick_array_bit_map::get_bitmap_tick_boundary(next_tick_array_start_index, tick_spacing);
+
+        let tick_array_offset_in_bitmap =
+            Self::tick_array_offset_in_bitmap(next_tick_array_start_index, tick_spacing);
+        if zero_for_one {
+            // tick from upper to lower
+            // find from highter bits to lower bits
+            let offset_bit_map = U512(tickarray_bitmap)
+                << (TICK_ARRAY_BITMAP_SIZE - 1 - tick_array_offset_in_bitmap);
+
+            if offset_bit_map.is_zero() {
+                // not found til to the end
+                (false, bitmap_min_tick_boundary)
+            } else {
+                let next_bit = offset_bit_map.leading_zeros() as i32;
+                (
+                    true,
+                    next_tick_array_start_index
+                        - next_bit * TickArrayState::tick_count(tick_spacing),
+                )
+            }
+        } else {
+            // tick from lower to upper
+            // find from lower bits to highter bits
+            let offset_bit_map = U512(tickarray_bitmap) >> tick_array_offset_in_bitmap;
+
+            if offset_bit_map.is_zero() {
+                // not found til to the end
+                (
+                    false,
+                    bitmap_max_tick_boundary - TickArrayState::tick_count(tick_spacing),
+                )
+            } else {
+                let next_bit = offset_bit_map.trailing_zeros() as i32;
+                (
+                    true,
+                    next_tick_array_start_index
+                        + next_bit * TickArrayState::tick_count(tick_spacing),
+                )
+            }
+        }
+    }
+
+    /// The bit position of the tick array in the bitmap it belongs to
+    pub fn tick_array_offset_in_bitmap(tick_array_start_index: i32, tick_spacing: u16) -> i32 {
+        let m = tick_array_start_index.abs()
+            % tick_array_bit_map::max_tick_in_tickarray_bitmap(tick_spacing);
+        let mut tick_array_offset_in_bitmap = m / TickArrayState::tick_count(tick_spacing);
+        if tick_array_start_index < 0 && m != 0 {
+            tick_array_offset_in_bitmap = TICK_ARRAY_BITMAP_SIZE - tick_array_offset_in_bitmap;
+        }
+        tick_array_offset_in_bitmap
+    }
+}
@@ -0,0 +1,112 @@
+//! Decode the events emitted by the program, from transaction logs or `emit_cpi` inner instructions.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::event::*;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClmmEvent {
+    ConfigChange(ConfigChangeEvent),
+    CreatePersonalPosition(CreatePersonalPositionEvent),
+    IncreaseLiquidity(IncreaseLiquidityEvent),
+    DecreaseLiquidity(DecreaseLiquidityEvent),
+    LiquidityCalculate(LiquidityCalculateEvent),
+    CollectPersonalFee(CollectPersonalFeeEvent),
+    UpdateRewardInfos(UpdateRewardInfosEvent),
+    PoolCreated(PoolCreatedEvent),
+    CollectProtocolFee(CollectProtocolFeeEvent),
+    Swap(SwapEvent),
+    LiquidityChange(LiquidityChangeEvent),
+}
+
+fn deserialize<T: AnchorDeserialize>(mut data: &[u8]) -> Option<T> {
+    T::deserialize(&mut data).ok()
+}
+
+impl ClmmEvent {
+    /// Decode an event from its serialized form, 8 bytes discriminator followed by the borsh data.
+    /// Returns None if the discriminator is not one of the program events.
+    pub fn decode(data: &[u8]) -> Option<ClmmEvent> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, data) = data.split_at(8);
+        let event = match discriminator {
+            d if d == ConfigChangeEvent::DISCRIMINATOR => {
+                ClmmEvent::ConfigChange(deserialize(data)?)
+            }
+            d if d == CreatePersonalPositionEvent::DISCRIMINATOR => {
+                ClmmEvent::CreatePersonalPosition(deserialize(data)?)
+            }
+            d if d == IncreaseLiquidityEvent::DISCRIMINATOR => {
+                ClmmEvent::IncreaseLiquidity(deserialize(data)?)
+            }
+            d if d == DecreaseLiquidityEvent::DISCRIMINATOR => {
+                ClmmEvent::DecreaseLiquidity(deserialize(data)?)
+            }
+            d if d == LiquidityCalculateEvent::DISCRIMINATOR => {
+                ClmmEvent::LiquidityCalculate(deserialize(data)?)
+            }
+            d if d == CollectPersonalFeeEvent::DISCRIMINATOR => {
+                ClmmEvent::CollectPersonalFee(deserialize(data)?)
+            }
+            d if d == UpdateRewardInfosEvent::DISCRIMINATOR => {
+                ClmmEvent::UpdateRewardInfos(deserialize(data)?)
+            }
+            d if d == PoolCreatedEvent::DISCRIMINATOR => ClmmEvent::PoolCreated(deserialize(data)?),
+            d if d == CollectProtocolFeeEvent::DISCRIMINATOR => {
+                ClmmEvent::CollectProtocolFee(deserialize(data)?)
+            }
+            d if d == SwapEvent::DISCRIMINATOR => ClmmEvent::Swap(deserialize(data)?),
+            d if d == LiquidityChangeEvent::DISCRIMINATOR => {
+                ClmmEvent::LiquidityChange(deserialize(data)?)
+            }
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    /// Decode an event from the data of an `emit_cpi` inner instruction invoking the program
+    pub fn decode_cpi_instruction_data(data: &[u8]) -> Option<ClmmEvent> {
+        ClmmEvent::decode(data.strip_prefix(EVENT_IX_TAG_LE.as_slice())?)
+    }
+
+    /// Decode an event from a single `Program data: <base64>` log line
+    pub fn decode_log(log: &str) -> Option<ClmmEvent> {
+        let data = STANDARD.decode(log.strip_prefix(PROGRAM_DATA)?).ok()?;
+        ClmmEvent::decode(&data)
+    }
+}
+
+/// Extract the events emitted by the program from the log messages of a transaction.
+/// `Program data:` lines logged by other programs, e.g. by a program invoking the clmm, are skipped.
+/// The events logged before a failed invoke are returned too, the caller checks the transaction status.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<ClmmEvent> {
+    let program_id = crate::id().to_string();
+    let mut invoke_stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for log in logs.iter().map(AsRef::as_ref) {
+        if log.starts_with(PROGRAM_DATA) {
+            if invoke_stack.last() == Some(&program_id.as_str()) {
+                if let Some(event) = ClmmEvent::decode_log(log) {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(program), Some("invoke")) => invoke_stack.push(program),
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    invoke_stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
@@ -0,0 +1,1158 @@
+//! Builders for every instruction of the program.
+//!
+//! Each builder derives the PDAs involved, appends the remaining accounts the program expects
+//! and returns an `Instruction` ready to be put into a transaction. Builders taking caller provided
+//! lists of accounts return an error instead of an instruction the program would reject.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::InstructionData;
+
+use super::{
+    find_metadata_address, get_associated_token_address, ASSOCIATED_TOKEN_PROGRAM_ID,
+    MEMO_PROGRAM_ID, METADATA_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use crate::account::*;
+use crate::context::InitializeRewardParam;
+use crate::error::ErrorCode;
+use crate::swap::{RouteHop, RouteQuote};
+use crate::token::MintCompatibility;
+use crate::{accounts, instruction, pda};
+
+fn build_instruction(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut accounts = accounts.to_account_metas(None);
+    accounts.extend(remaining_accounts);
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: data.data(),
+    }
+}
+
+/// The bitmap extension is passed as the first remaining account when a position's tick arrays
+/// are out of the range of the pool's default bitmap.
+fn position_bitmap_extension_account(
+    pool_id: &Pubkey,
+    pool_state: &PoolState,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Option<AccountMeta> {
+    let tick_array_lower_start_index =
+        TickArrayState::get_array_start_index(tick_lower_index, pool_state.tick_spacing);
+    let tick_array_upper_start_index =
+        TickArrayState::get_array_start_index(tick_upper_index, pool_state.tick_spacing);
+    pool_state
+        .is_overflow_default_tickarray_bitmap(vec![
+            tick_array_lower_start_index,
+            tick_array_upper_start_index,
+        ])
+        .then(|| AccountMeta::new(pda::find_bitmap_extension_address(pool_id).0, false))
+}
+
+/// Reward accounts passed as remaining accounts by `decrease_liquidity(_v2)`,
+/// one group per initialized reward: reward vault, recipient token account and, for v2, reward mint.
+fn reward_accounts(
+    pool_state: &PoolState,
+    reward_recipient_token_accounts: &[Pubkey],
+    with_mint: bool,
+) -> Result<Vec<AccountMeta>> {
+    let reward_infos: Vec<&RewardInfo> = pool_state
+        .reward_infos
+        .iter()
+        .filter(|reward_info| reward_info.token_mint != Pubkey::default())
+        .collect();
+    require_eq!(
+        reward_infos.len(),
+        reward_recipient_token_accounts.len(),
+        ErrorCode::InvalidRewardInputAccountNumber
+    );
+    let mut accounts = Vec::new();
+    for (reward_info, recipient) in reward_infos
+        .into_iter()
+        .zip(reward_recipient_token_accounts)
+    {
+        accounts.push(AccountMeta::new(reward_info.token_vault, false));
+        accounts.push(AccountMeta::new(*recipient, false));
+        if with_mint {
+            accounts.push(AccountMeta::new_readonly(reward_info.token_mint, false));
+        }
+    }
+    Ok(accounts)
+}
+
+/// The reward info at `reward_index`, which must be smaller than `REWARD_NUM`
+fn reward_info(pool_state: &PoolState, reward_index: u8) -> Result<RewardInfo> {
+    Ok(*pool_state
+        .reward_infos
+        .get(reward_index as usize)
+        .ok_or(ErrorCode::InvalidRewardIndex)?)
+}
+
+/// The `SupportMintAssociated` accounts passed as remaining accounts by `create_pool` and
+/// `initialize_reward`, for the mints whose extensions are not supported by default
+fn support_mint_accounts(mints: &[(&Pubkey, Option<&MintCompatibility>)]) -> Vec<AccountMeta> {
+    mints
+        .iter()
+        .filter(|(_, compatibility)| compatibility.is_some_and(|c| !c.is_supported()))
+        .map(|(mint, _)| {
+            AccountMeta::new_readonly(pda::find_support_mint_associated_address(mint).0, false)
+        })
+        .collect()
+}
+
+/// Remaining accounts of a swap: the bitmap extension followed by the tick arrays in swap order
+fn swap_remaining_accounts(pool_id: &Pubkey, tick_array_start_indexes: &[i32]) -> Vec<AccountMeta> {
+    let mut accounts = vec![AccountMeta::new_readonly(
+        pda::find_bitmap_extension_address(pool_id).0,
+        false,
+    )];
+    accounts.extend(
+        tick_array_start_indexes
+            .iter()
+            .map(|index| AccountMeta::new(pda::find_tick_array_address(pool_id, *index).0, false)),
+    );
+    accounts
+}
+
+pub fn create_amm_config(
+    owner: Pubkey,
+    index: u16,
+    tick_spacing: u16,
+    trade_fee_rate: u32,
+    protocol_fee_rate: u32,
+    fund_fee_rate: u32,
+) -> Instruction {
+    build_instruction(
+        accounts::CreateAmmConfig {
+            owner,
+            amm_config: pda::find_amm_config_address(index).0,
+            system_program: system_program::ID,
+        },
+        instruction::CreateAmmConfig {
+            index,
+            tick_spacing,
+            trade_fee_rate,
+            protocol_fee_rate,
+            fund_fee_rate,
+        },
+        vec![],
+    )
+}
+
+/// The `param` of `update_amm_config` with its new value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmmConfigUpdate {
+    TradeFeeRate(u32),
+    ProtocolFeeRate(u32),
+    FundFeeRate(u32),
+    NewOwner(Pubkey),
+    NewFundOwner(Pubkey),
+}
+
+impl AmmConfigUpdate {
+    pub fn param(&self) -> u8 {
+        match self {
+            AmmConfigUpdate::TradeFeeRate(_) => 0,
+            AmmConfigUpdate::ProtocolFeeRate(_) => 1,
+            AmmConfigUpdate::FundFeeRate(_) => 2,
+            AmmConfigUpdate::NewOwner(_) => 3,
+            AmmConfigUpdate::NewFundOwner(_) => 4,
+        }
+    }
+
+    /// The `value` argument, unused when updating an owner
+    pub fn value(&self) -> u32 {
+        match self {
+            AmmConfigUpdate::TradeFeeRate(rate)
+            | AmmConfigUpdate::ProtocolFeeRate(rate)
+            | AmmConfigUpdate::FundFeeRate(rate) => *rate,
+            AmmConfigUpdate::NewOwner(_) | AmmConfigUpdate::NewFundOwner(_) => 0,
+        }
+    }
+
+    /// New owners are passed as the first remaining account
+    pub fn remaining_accounts(&self) -> Vec<AccountMeta> {
+        match self {
+            AmmConfigUpdate::NewOwner(owner) | AmmConfigUpdate::NewFundOwner(owner) => {
+                vec![AccountMeta::new_readonly(*owner, false)]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// The `param` of `update_operation_account` with the keys to add or remove
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OperationUpdate {
+    AddOwners(Vec<Pubkey>),
+    RemoveOwners(Vec<Pubkey>),
+    AddWhitelistMints(Vec<Pubkey>),
+    RemoveWhitelistMints(Vec<Pubkey>),
+}
+
+impl OperationUpdate {
+    pub fn param(&self) -> u8 {
+        match self {
+            OperationUpdate::AddOwners(_) => 0,
+            OperationUpdate::RemoveOwners(_) => 1,
+            OperationUpdate::AddWhitelistMints(_) => 2,
+            OperationUpdate::RemoveWhitelistMints(_) => 3,
+        }
+    }
+
+    pub fn keys(&self) -> &[Pubkey] {
+        match self {
+            OperationUpdate::AddOwners(keys)
+            | OperationUpdate::RemoveOwners(keys)
+            | OperationUpdate::AddWhitelistMints(keys)
+            | OperationUpdate::RemoveWhitelistMints(keys) => keys,
+        }
+    }
+}
+
+pub fn update_amm_config(
+    owner: Pubkey,
+    amm_config: Pubkey,
+    update: AmmConfigUpdate,
+) -> Instruction {
+    build_instruction(
+        accounts::UpdateAmmConfig { owner, amm_config },
+        instruction::UpdateAmmConfig {
+            param: update.param(),
+            value: update.value(),
+        },
+        update.remaining_accounts(),
+    )
+}
+
+/// `token_mint_0` must be smaller than `token_mint_1`, `token_program_0/1` are the owners of the mints.
+/// `mint_0_compatibility` and `mint_1_compatibility` come from `check_mint_compatibility`, the support
+/// mint accounts of mints with not supported extensions are appended. `None` is a supported mint,
+/// e.g. an SPL Token mint, and adds no account.
+pub fn create_pool(
+    pool_creator: Pubkey,
+    amm_config: Pubkey,
+    token_mint_0: Pubkey,
+    token_mint_1: Pubkey,
+    token_program_0: Pubkey,
+    token_program_1: Pubkey,
+    mint_0_compatibility: Option<&MintCompatibility>,
+    mint_1_compatibility: Option<&MintCompatibility>,
+    sqrt_price_x64: u128,
+    open_time: u64,
+) -> Instruction {
+    let pool_state = pda::find_pool_address(&amm_config, &token_mint_0, &token_mint_1).0;
+    build_instruction(
+        accounts::CreatePool {
+            pool_creator,
+            amm_config,
+            pool_state,
+            token_mint_0,
+            token_mint_1,
+            token_vault_0: pda::find_pool_vault_address(&pool_state, &token_mint_0).0,
+            token_vault_1: pda::find_pool_vault_address(&pool_state, &token_mint_1).0,
+            observation_state: pda::find_observation_address(&pool_state).0,
+            tick_array_bitmap: pda::find_bitmap_extension_address(&pool_state).0,
+            token_program_0,
+            token_program_1,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::CreatePool {
+            sqrt_price_x64,
+            open_time,
+        },
+        support_mint_accounts(&[
+            (&token_mint_0, mint_0_compatibility),
+            (&token_mint_1, mint_1_compatibility),
+        ]),
+    )
+}
+
+/// `status` can be built with `PoolStatusBuilder`
+pub fn update_pool_status(authority: Pubkey, pool_state: Pubkey, status: u8) -> Instruction {
+    build_instruction(
+        accounts::UpdatePoolStatus {
+            authority,
+            pool_state,
+        },
+        instruction::UpdatePoolStatus { status },
+        vec![],
+    )
+}
+
+pub fn create_operation_account(owner: Pubkey) -> Instruction {
+    build_instruction(
+        accounts::CreateOperationAccount {
+            owner,
+            operation_state: pda::find_operation_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::CreateOperationAccount {},
+        vec![],
+    )
+}
+
+pub fn update_operation_account(owner: Pubkey, update: OperationUpdate) -> Instruction {
+    build_instruction(
+        accounts::UpdateOperationAccount {
+            owner,
+            operation_state: pda::find_operation_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateOperationAccount {
+            param: update.param(),
+            keys: update.keys().to_vec(),
+        },
+        vec![],
+    )
+}
+
+pub fn create_support_mint_associated(owner: Pubkey, token_mint: Pubkey) -> Instruction {
+    build_instruction(
+        accounts::CreateSupportMintAssociated {
+            owner,
+            token_mint,
+            support_mint_associated: pda::find_support_mint_associated_address(&token_mint).0,
+            system_program: system_program::ID,
+        },
+        instruction::CreateSupportMintAssociated {},
+        vec![],
+    )
+}
+
+pub fn transfer_reward_owner(
+    authority: Pubkey,
+    pool_state: Pubkey,
+    new_owner: Pubkey,
+) -> Instruction {
+    build_instruction(
+        accounts::TransferRewardOwner {
+            authority,
+            pool_state,
+        },
+        instruction::TransferRewardOwner { new_owner },
+        vec![],
+    )
+}
+
+/// `reward_mint_compatibility` comes from `check_mint_compatibility`, the support mint account
+/// is appended if the reward mint has not supported extensions. `None` is a supported mint.
+pub fn initialize_reward(
+    reward_funder: Pubkey,
+    funder_token_account: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    reward_token_mint: Pubkey,
+    reward_token_program: Pubkey,
+    reward_mint_compatibility: Option<&MintCompatibility>,
+    param: InitializeRewardParam,
+) -> Instruction {
+    build_instruction(
+        accounts::InitializeReward {
+            reward_funder,
+            funder_token_account,
+            amm_config: pool_state.amm_config,
+            pool_state: pool_id,
+            operation_state: pda::find_operation_address().0,
+            reward_token_mint,
+            reward_token_vault: pda::find_reward_vault_address(&pool_id, &reward_token_mint).0,
+            reward_token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeReward { param },
+        support_mint_accounts(&[(&reward_token_mint, reward_mint_compatibility)]),
+    )
+}
+
+pub fn collect_remaining_rewards(
+    reward_funder: Pubkey,
+    funder_token_account: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    reward_index: u8,
+) -> Result<Instruction> {
+    let reward_info = reward_info(pool_state, reward_index)?;
+    Ok(build_instruction(
+        accounts::CollectRemainingRewards {
+            reward_funder,
+            funder_token_account,
+            pool_state: pool_id,
+            reward_token_vault: reward_info.token_vault,
+            reward_vault_mint: reward_info.token_mint,
+            token_program: TOKEN_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+            memo_program: MEMO_PROGRAM_ID,
+        },
+        instruction::CollectRemainingRewards { reward_index },
+        vec![],
+    ))
+}
+
+pub fn update_reward_infos(pool_state: Pubkey) -> Instruction {
+    build_instruction(
+        accounts::UpdateRewardInfos { pool_state },
+        instruction::UpdateRewardInfos {},
+        vec![],
+    )
+}
+
+/// `authority_token_account` is the authority's reward token account, it funds the reward vault
+/// when the new parameters require more reward tokens than already deposited.
+pub fn set_reward_params(
+    authority: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    reward_index: u8,
+    emissions_per_second_x64: u128,
+    open_time: u64,
+    end_time: u64,
+    authority_token_account: Pubkey,
+) -> Result<Instruction> {
+    let reward_info = reward_info(pool_state, reward_index)?;
+    Ok(build_instruction(
+        accounts::SetRewardParams {
+            authority,
+            amm_config: pool_state.amm_config,
+            pool_state: pool_id,
+            operation_state: pda::find_operation_address().0,
+            token_program: TOKEN_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+        },
+        instruction::SetRewardParams {
+            reward_index,
+            emissions_per_second_x64,
+            open_time,
+            end_time,
+        },
+        vec![
+            AccountMeta::new(reward_info.token_vault, false),
+            AccountMeta::new(authority_token_account, false),
+            AccountMeta::new_readonly(reward_info.token_mint, false),
+        ],
+    ))
+}
+
+pub fn collect_protocol_fee(
+    owner: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    recipient_token_account_0: Pubkey,
+    recipient_token_account_1: Pubkey,
+    amount_0_requested: u64,
+    amount_1_requested: u64,
+) -> Instruction {
+    build_instruction(
+        accounts::CollectProtocolFee {
+            owner,
+            pool_state: pool_id,
+            amm_config: pool_state.amm_config,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            vault_0_mint: pool_state.token_mint_0,
+            vault_1_mint: pool_state.token_mint_1,
+            recipient_token_account_0,
+            recipient_token_account_1,
+            token_program: TOKEN_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+        },
+        instruction::CollectProtocolFee {
+            amount_0_requested,
+            amount_1_requested,
+        },
+        vec![],
+    )
+}
+
+pub fn collect_fund_fee(
+    owner: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    recipient_token_account_0: Pubkey,
+    recipient_token_account_1: Pubkey,
+    amount_0_requested: u64,
+    amount_1_requested: u64,
+) -> Instruction {
+    build_instruction(
+        accounts::CollectFundFee {
+            owner,
+            pool_state: pool_id,
+            amm_config: pool_state.amm_config,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            vault_0_mint: pool_state.token_mint_0,
+            vault_1_mint: pool_state.token_mint_1,
+            recipient_token_account_0,
+            recipient_token_account_1,
+            token_program: TOKEN_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+        },
+        instruction::CollectFundFee {
+            amount_0_requested,
+            amount_1_requested,
+        },
+        vec![],
+    )
+}
+
+/// `position_nft_mint` is a new keypair which must sign the transaction
+pub fn open_position(
+    payer: Pubkey,
+    position_nft_owner: Pubkey,
+    position_nft_mint: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    token_account_0: Pubkey,
+    token_account_1: Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+) -> Instruction {
+    let tick_array_lower_start_index =
+        TickArrayState::get_array_start_index(tick_lower_index, pool_state.tick_spacing);
+    let tick_array_upper_start_index =
+        TickArrayState::get_array_start_index(tick_upper_index, pool_state.tick_spacing);
+    build_instruction(
+        accounts::OpenPosition {
+            payer,
+            position_nft_owner,
+            position_nft_mint,
+            position_nft_account: get_associated_token_address(
+                &position_nft_owner,
+                &position_nft_mint,
+                &TOKEN_PROGRAM_ID,
+            ),
+            metadata_account: find_metadata_address(&position_nft_mint),
+            pool_state: pool_id,
+            protocol_position: pda::find_protocol_position_address(
+                &pool_id,
+                tick_lower_index,
+                tick_upper_index,
+            )
+            .0,
+            tick_array_lower: pda::find_tick_array_address(&pool_id, tick_array_lower_start_index)
+                .0,
+            tick_array_upper: pda::find_tick_array_address(&pool_id, tick_array_upper_start_index)
+                .0,
+            personal_position: pda::find_personal_position_address(&position_nft_mint).0,
+            token_account_0,
+            token_account_1,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: TOKEN_PROGRAM_ID,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            metadata_program: METADATA_PROGRAM_ID,
+        },
+        instruction::OpenPosition {
+            tick_lower_index,
+            tick_upper_index,
+            tick_array_lower_start_index,
+            tick_array_upper_start_index,
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+        },
+        position_bitmap_extension_account(&pool_id, pool_state, tick_lower_index, tick_upper_index)
+            .into_iter()
+            .collect(),
+    )
+}
+
+/// `position_nft_mint` is a new keypair which must sign the transaction
+pub fn open_position_v2(
+    payer: Pubkey,
+    position_nft_owner: Pubkey,
+    position_nft_mint: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    token_account_0: Pubkey,
+    token_account_1: Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    with_metadata: bool,
+    base_flag: Option<bool>,
+) -> Instruction {
+    let tick_array_lower_start_index =
+        TickArrayState::get_array_start_index(tick_lower_index, pool_state.tick_spacing);
+    let tick_array_upper_start_index =
+        TickArrayState::get_array_start_index(tick_upper_index, pool_state.tick_spacing);
+    build_instruction(
+        accounts::OpenPositionV2 {
+            payer,
+            position_nft_owner,
+            position_nft_mint,
+            position_nft_account: get_associated_token_address(
+                &position_nft_owner,
+                &position_nft_mint,
+                &TOKEN_PROGRAM_ID,
+            ),
+            metadata_account: find_metadata_address(&position_nft_mint),
+            pool_state: pool_id,
+            protocol_position: pda::find_protocol_position_address(
+                &pool_id,
+                tick_lower_index,
+                tick_upper_index,
+            )
+            .0,
+            tick_array_lower: pda::find_tick_array_address(&pool_id, tick_array_lower_start_index)
+                .0,
+            tick_array_upper: pda::find_tick_array_address(&pool_id, tick_array_upper_start_index)
+                .0,
+            personal_position: pda::find_personal_position_address(&position_nft_mint).0,
+            token_account_0,
+            token_account_1,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: TOKEN_PROGRAM_ID,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            metadata_program: METADATA_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+            vault_0_mint: pool_state.token_mint_0,
+            vault_1_mint: pool_state.token_mint_1,
+        },
+        instruction::OpenPositionV2 {
+            tick_lower_index,
+            tick_upper_index,
+            tick_array_lower_start_index,
+            tick_array_upper_start_index,
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+            with_metadata,
+            base_flag,
+        },
+        position_bitmap_extension_account(&pool_id, pool_state, tick_lower_index, tick_upper_index)
+            .into_iter()
+            .collect(),
+    )
+}
+
+/// `position_nft_mint` is a new keypair which must sign the transaction
+pub fn open_position_with_token22_nft(
+    payer: Pubkey,
+    position_nft_owner: Pubkey,
+    position_nft_mint: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    token_account_0: Pubkey,
+    token_account_1: Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    with_metadata: bool,
+    base_flag: Option<bool>,
+) -> Instruction {
+    let tick_array_lower_start_index =
+        TickArrayState::get_array_start_index(tick_lower_index, pool_state.tick_spacing);
+    let tick_array_upper_start_index =
+        TickArrayState::get_array_start_index(tick_upper_index, pool_state.tick_spacing);
+    build_instruction(
+        accounts::OpenPositionWithToken22Nft {
+            payer,
+            position_nft_owner,
+            position_nft_mint,
+            position_nft_account: get_associated_token_address(
+                &position_nft_owner,
+                &position_nft_mint,
+                &TOKEN_2022_PROGRAM_ID,
+            ),
+            pool_state: pool_id,
+            protocol_position: pda::find_protocol_position_address(
+                &pool_id,
+                tick_lower_index,
+                tick_upper_index,
+            )
+            .0,
+            tick_array_lower: pda::find_tick_array_address(&pool_id, tick_array_lower_start_index)
+                .0,
+            tick_array_upper: pda::find_tick_array_address(&pool_id, tick_array_upper_start_index)
+                .0,
+            personal_position: pda::find_personal_position_address(&position_nft_mint).0,
+            token_account_0,
+            token_account_1,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: TOKEN_PROGRAM_ID,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+            vault_0_mint: pool_state.token_mint_0,
+            vault_1_mint: pool_state.token_mint_1,
+        },
+        instruction::OpenPositionWithToken22Nft {
+            tick_lower_index,
+            tick_upper_index,
+            tick_array_lower_start_index,
+            tick_array_upper_start_index,
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+            with_metadata,
+            base_flag,
+        },
+        position_bitmap_extension_account(&pool_id, pool_state, tick_lower_index, tick_upper_index)
+            .into_iter()
+            .collect(),
+    )
+}
+
+/// `nft_token_program` is the owner of the position NFT mint, token program or token program 2022
+pub fn close_position(
+    nft_owner: Pubkey,
+    position_nft_mint: Pubkey,
+    nft_token_program: Pubkey,
+) -> Instruction {
+    build_instruction(
+        accounts::ClosePosition {
+            nft_owner,
+            position_nft_mint,
+            position_nft_account: get_associated_token_address(
+                &nft_owner,
+                &position_nft_mint,
+                &nft_token_program,
+            ),
+            personal_position: pda::find_personal_position_address(&position_nft_mint).0,
+            system_program: system_program::ID,
+            token_program: nft_token_program,
+        },
+        instruction::ClosePosition {},
+        vec![],
+    )
+}
+
+pub fn increase_liquidity(
+    nft_owner: Pubkey,
+    nft_account: Pubkey,
+    pool_state: &PoolState,
+    personal_position: &PersonalPositionState,
+    token_account_0: Pubkey,
+    token_account_1: Pubkey,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+) -> Instruction {
+    let pool_id = personal_position.pool_id;
+    let (tick_lower_index, tick_upper_index) = (
+        personal_position.tick_lower_index,
+        personal_position.tick_upper_index,
+    );
+    build_instruction(
+        accounts::IncreaseLiquidity {
+            nft_owner,
+            nft_account,
+            pool_state: pool_id,
+            protocol_position: pda::find_protocol_position_address(
+                &pool_id,
+                tick_lower_index,
+                tick_upper_index,
+            )
+            .0,
+            personal_position: pda::find_personal_position_address(&personal_position.nft_mint).0,
+            tick_array_lower: pda::find_tick_array_address(
+                &pool_id,
+                TickArrayState::get_array_start_index(tick_lower_index, pool_state.tick_spacing),
+            )
+            .0,
+            tick_array_upper: pda::find_tick_array_address(
+                &pool_id,
+                TickArrayState::get_array_start_index(tick_upper_index, pool_state.tick_spacing),
+            )
+            .0,
+            token_account_0,
+            token_account_1,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        instruction::IncreaseLiquidity {
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+        },
+        position_bitmap_extension_account(&pool_id, pool_state, tick_lower_index, tick_upper_index)
+            .into_iter()
+            .collect(),
+    )
+}
+
+pub fn increase_liquidity_v2(
+    nft_owner: Pubkey,
+    nft_account: Pubkey,
+    pool_state: &PoolState,
+    personal_position: &PersonalPositionState,
+    token_account_0: Pubkey,
+    token_account_1: Pubkey,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    base_flag: Option<bool>,
+) -> Instruction {
+    let pool_id = personal_position.pool_id;
+    let (tick_lower_index, tick_upper_index) = (
+        personal_position.tick_lower_index,
+        personal_position.tick_upper_index,
+    );
+    build_instruction(
+        accounts::IncreaseLiquidityV2 {
+            nft_owner,
+            nft_account,
+            pool_state: pool_id,
+            protocol_position: pda::find_protocol_position_address(
+                &pool_id,
+                tick_lower_index,
+                tick_upper_index,
+            )
+            .0,
+            personal_position: pda::find_personal_position_address(&personal_position.nft_mint).0,
+            tick_array_lower: pda::find_tick_array_address(
+                &pool_id,
+                TickArrayState::get_array_start_index(tick_lower_index, pool_state.tick_spacing),
+            )
+            .0,
+            tick_array_upper: pda::find_tick_array_address(
+                &pool_id,
+                TickArrayState::get_array_start_index(tick_upper_index, pool_state.tick_spacing),
+            )
+            .0,
+            token_account_0,
+            token_account_1,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            token_program: TOKEN_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+            vault_0_mint: pool_state.token_mint_0,
+            vault_1_mint: pool_state.token_mint_1,
+        },
+        instruction::IncreaseLiquidityV2 {
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+            base_flag,
+        },
+        position_bitmap_extension_account(&pool_id, pool_state, tick_lower_index, tick_upper_index)
+            .into_iter()
+            .collect(),
+    )
+}
+
+/// `reward_recipient_token_accounts` receive the rewards of the pool's initialized reward infos, in order,
+/// one account is required for every initialized reward info.
+pub fn decrease_liquidity(
+    nft_owner: Pubkey,
+    nft_account: Pubkey,
+    pool_state: &PoolState,
+    personal_position: &PersonalPositionState,
+    recipient_token_account_0: Pubkey,
+    recipient_token_account_1: Pubkey,
+    reward_recipient_token_accounts: &[Pubkey],
+    liquidity: u128,
+    amount_0_min: u64,
+    amount_1_min: u64,
+) -> Result<Instruction> {
+    let pool_id = personal_position.pool_id;
+    let (tick_lower_index, tick_upper_index) = (
+        personal_position.tick_lower_index,
+        personal_position.tick_upper_index,
+    );
+    let mut remaining_accounts: Vec<AccountMeta> =
+        position_bitmap_extension_account(&pool_id, pool_state, tick_lower_index, tick_upper_index)
+            .into_iter()
+            .collect();
+    remaining_accounts.extend(reward_accounts(
+        pool_state,
+        reward_recipient_token_accounts,
+        false,
+    )?);
+    Ok(build_instruction(
+        accounts::DecreaseLiquidity {
+            nft_owner,
+            nft_account,
+            personal_position: pda::find_personal_position_address(&personal_position.nft_mint).0,
+            pool_state: pool_id,
+            protocol_position: pda::find_protocol_position_address(
+                &pool_id,
+                tick_lower_index,
+                tick_upper_index,
+            )
+            .0,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            tick_array_lower: pda::find_tick_array_address(
+                &pool_id,
+                TickArrayState::get_array_start_index(tick_lower_index, pool_state.tick_spacing),
+            )
+            .0,
+            tick_array_upper: pda::find_tick_array_address(
+                &pool_id,
+                TickArrayState::get_array_start_index(tick_upper_index, pool_state.tick_spacing),
+            )
+            .0,
+            recipient_token_account_0,
+            recipient_token_account_1,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        instruction::DecreaseLiquidity {
+            liquidity,
+            amount_0_min,
+            amount_1_min,
+        },
+        remaining_accounts,
+    ))
+}
+
+/// `reward_recipient_token_accounts` receive the rewards of the pool's initialized reward infos, in order,
+/// one account is required for every initialized reward info.
+pub fn decrease_liquidity_v2(
+    nft_owner: Pubkey,
+    nft_account: Pubkey,
+    pool_state: &PoolState,
+    personal_position: &PersonalPositionState,
+    recipient_token_account_0: Pubkey,
+    recipient_token_account_1: Pubkey,
+    reward_recipient_token_accounts: &[Pubkey],
+    liquidity: u128,
+    amount_0_min: u64,
+    amount_1_min: u64,
+) -> Result<Instruction> {
+    let pool_id = personal_position.pool_id;
+    let (tick_lower_index, tick_upper_index) = (
+        personal_position.tick_lower_index,
+        personal_position.tick_upper_index,
+    );
+    let mut remaining_accounts: Vec<AccountMeta> =
+        position_bitmap_extension_account(&pool_id, pool_state, tick_lower_index, tick_upper_index)
+            .into_iter()
+            .collect();
+    remaining_accounts.extend(reward_accounts(
+        pool_state,
+        reward_recipient_token_accounts,
+        true,
+    )?);
+    Ok(build_instruction(
+        accounts::DecreaseLiquidityV2 {
+            nft_owner,
+            nft_account,
+            personal_position: pda::find_personal_position_address(&personal_position.nft_mint).0,
+            pool_state: pool_id,
+            protocol_position: pda::find_protocol_position_address(
+                &pool_id,
+                tick_lower_index,
+                tick_upper_index,
+            )
+            .0,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            tick_array_lower: pda::find_tick_array_address(
+                &pool_id,
+                TickArrayState::get_array_start_index(tick_lower_index, pool_state.tick_spacing),
+            )
+            .0,
+            tick_array_upper: pda::find_tick_array_address(
+                &pool_id,
+                TickArrayState::get_array_start_index(tick_upper_index, pool_state.tick_spacing),
+            )
+            .0,
+            recipient_token_account_0,
+            recipient_token_account_1,
+            token_program: TOKEN_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+            memo_program: MEMO_PROGRAM_ID,
+            vault_0_mint: pool_state.token_mint_0,
+            vault_1_mint: pool_state.token_mint_1,
+        },
+        instruction::DecreaseLiquidityV2 {
+            liquidity,
+            amount_0_min,
+            amount_1_min,
+        },
+        remaining_accounts,
+    ))
+}
+
+/// `tick_array_start_indexes` are the tick arrays the swap walks through, in order,
+/// e.g. `SwapResult::tick_array_start_indexes`, at least one is required.
+pub fn swap(
+    payer: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    input_token_account: Pubkey,
+    output_token_account: Pubkey,
+    zero_for_one: bool,
+    tick_array_start_indexes: &[i32],
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+) -> Result<Instruction> {
+    let (input_vault, output_vault) = if zero_for_one {
+        (pool_state.token_vault_0, pool_state.token_vault_1)
+    } else {
+        (pool_state.token_vault_1, pool_state.token_vault_0)
+    };
+    let (first_tick_array, tick_array_start_indexes) = tick_array_start_indexes
+        .split_first()
+        .ok_or(ErrorCode::NotEnoughTickArrayAccount)?;
+    Ok(build_instruction(
+        accounts::SwapSingle {
+            payer,
+            amm_config: pool_state.amm_config,
+            pool_state: pool_id,
+            input_token_account,
+            output_token_account,
+            input_vault,
+            output_vault,
+            observation_state: pool_state.observation_key,
+            token_program: TOKEN_PROGRAM_ID,
+            tick_array: pda::find_tick_array_address(&pool_id, *first_tick_array).0,
+        },
+        instruction::Swap {
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit_x64,
+            is_base_input,
+        },
+        swap_remaining_accounts(&pool_id, tick_array_start_indexes),
+    ))
+}
+
+/// `tick_array_start_indexes` are the tick arrays the swap walks through, in order,
+/// e.g. `SwapResult::tick_array_start_indexes`, at least one is required.
+pub fn swap_v2(
+    payer: Pubkey,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    input_token_account: Pubkey,
+    output_token_account: Pubkey,
+    zero_for_one: bool,
+    tick_array_start_indexes: &[i32],
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+) -> Result<Instruction> {
+    let (input_vault, output_vault, input_vault_mint, output_vault_mint) = if zero_for_one {
+        (
+            pool_state.token_vault_0,
+            pool_state.token_vault_1,
+            pool_state.token_mint_0,
+            pool_state.token_mint_1,
+        )
+    } else {
+        (
+            pool_state.token_vault_1,
+            pool_state.token_vault_0,
+            pool_state.token_mint_1,
+            pool_state.token_mint_0,
+        )
+    };
+    require!(
+        !tick_array_start_indexes.is_empty(),
+        ErrorCode::NotEnoughTickArrayAccount
+    );
+    Ok(build_instruction(
+        accounts::SwapSingleV2 {
+            payer,
+            amm_config: pool_state.amm_config,
+            pool_state: pool_id,
+            input_token_account,
+            output_token_account,
+            input_vault,
+            output_vault,
+            observation_state: pool_state.observation_key,
+            token_program: TOKEN_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+            memo_program: MEMO_PROGRAM_ID,
+            input_vault_mint,
+            output_vault_mint,
+        },
+        instruction::SwapV2 {
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit_x64,
+            is_base_input,
+        },
+        swap_remaining_accounts(&pool_id, tick_array_start_indexes),
+    ))
+}
+
+/// The remaining accounts of one hop of `swap_router_base_in`: amm config, pool, output token account,
+/// input vault, output vault, output mint and observation, followed by the bitmap extension and the tick arrays.
+fn route_hop_accounts(hop: &RouteHop, tick_array_start_indexes: &[i32]) -> Vec<AccountMeta> {
+    let pool_state = &hop.pool_state;
+    let (input_vault, output_vault) = if hop.zero_for_one {
+        (pool_state.token_vault_0, pool_state.token_vault_1)
+    } else {
+        (pool_state.token_vault_1, pool_state.token_vault_0)
+    };
+    let mut accounts = vec![
+        AccountMeta::new_readonly(pool_state.amm_config, false),
+        AccountMeta::new(hop.pool_id, false),
+        AccountMeta::new(hop.output_token_account, false),
+        AccountMeta::new(input_vault, false),
+        AccountMeta::new(output_vault, false),
+        AccountMeta::new_readonly(hop.output_mint(), false),
+        AccountMeta::new(pool_state.observation_key, false),
+    ];
+    accounts.extend(swap_remaining_accounts(
+        &hop.pool_id,
+        tick_array_start_indexes,
+    ));
+    accounts
+}
+
+/// `quote` is the result of `quote_route_base_in` over the same `hops`, it provides the amount in
+/// and the tick arrays of every hop. `hops` must not be empty.
+pub fn swap_router_base_in(
+    payer: Pubkey,
+    input_token_account: Pubkey,
+    hops: &[RouteHop],
+    quote: &RouteQuote,
+    amount_out_minimum: u64,
+) -> Result<Instruction> {
+    let first_hop = hops.first().ok_or(ProgramError::InvalidArgument)?;
+    if hops.len() != quote.hops.len() {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+    let mut remaining_accounts = Vec::new();
+    for (hop, hop_quote) in hops.iter().zip(&quote.hops) {
+        let tick_array_start_indexes = &hop_quote.swap_result.tick_array_start_indexes;
+        require!(
+            !tick_array_start_indexes.is_empty(),
+            ErrorCode::NotEnoughTickArrayAccount
+        );
+        remaining_accounts.extend(route_hop_accounts(hop, tick_array_start_indexes));
+    }
+    Ok(build_instruction(
+        accounts::SwapRouterBaseIn {
+            payer,
+            input_token_account,
+            input_token_mint: first_hop.input_mint(),
+            token_program: TOKEN_PROGRAM_ID,
+            token_program_2022: TOKEN_2022_PROGRAM_ID,
+            memo_program: MEMO_PROGRAM_ID,
+        },
+        instruction::SwapRouterBaseIn {
+            amount_in: quote.amount_in,
+            amount_out_minimum,
+        },
+        remaining_accounts,
+    ))
+}
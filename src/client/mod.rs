@@ -0,0 +1,58 @@
+//! Off-chain helpers to build transactions for the Raydium CLMM program.
+
+use anchor_lang::prelude::*;
+use anchor_lang::pubkey;
+
+use crate::account::SwapTickArrays;
+use crate::pda;
+
+pub mod events;
+pub mod instructions;
+
+/// SPL token program
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+/// SPL token program 2022
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+/// SPL memo program
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+/// SPL associated token account program
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+/// Metaplex token metadata program
+pub const METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Address of the associated token account of `owner` for `mint` under `token_program`
+pub fn get_associated_token_address(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Address of the metaplex metadata account of `mint`
+pub fn find_metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Addresses of `tick_arrays` of the pool, in the order the swap instructions expect them.
+/// They follow the bitmap extension account in the remaining accounts when
+/// `SwapTickArrays::is_bitmap_extension_needed` is set.
+pub fn get_swap_tick_array_addresses(
+    pool_id: &Pubkey,
+    tick_arrays: &SwapTickArrays,
+) -> Vec<Pubkey> {
+    tick_arrays
+        .start_indexes
+        .iter()
+        .map(|start_index| pda::find_tick_array_address(pool_id, *start_index).0)
+        .collect()
+}
@@ -1,9 +1,24 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::memo::Memo;
+#[cfg(feature = "metadata")]
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::{self, Token};
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
 
 use crate::account::*;
 
-#[account]
-pub struct AccountPlaceholder {}
+/// The Metaplex token metadata program, the `metadata` feature uses the one of `anchor-spl`
+#[cfg(not(feature = "metadata"))]
+#[derive(Clone)]
+pub struct Metadata;
+
+#[cfg(not(feature = "metadata"))]
+impl anchor_lang::Id for Metadata {
+    fn id() -> Pubkey {
+        crate::client::METADATA_PROGRAM_ID
+    }
+}
 
 #[derive(Accounts)]
 #[instruction(index: u16)]
@@ -16,7 +31,7 @@ pub struct CreateAmmConfig<'info> {
     #[account(mut)]
     pub amm_config: Box<Account<'info, AmmConfig>>,
 
-    pub system_program: Account<'info, AccountPlaceholder>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -42,18 +57,18 @@ pub struct CreatePool<'info> {
     pub pool_state: AccountLoader<'info, PoolState>,
 
     /// Token_0 mint, the key must be smaller then token_1 mint.
-    pub token_mint_0: Account<'info, AccountPlaceholder>,
+    pub token_mint_0: Box<InterfaceAccount<'info, Mint>>,
 
     /// Token_1 mint
-    pub token_mint_1: Account<'info, AccountPlaceholder>,
+    pub token_mint_1: Box<InterfaceAccount<'info, Mint>>,
 
-    /// Token_0 vault for the pool
+    /// CHECK: Token_0 vault for the pool
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: UncheckedAccount<'info>,
 
-    /// Token_1 vault for the pool
+    /// CHECK: Token_1 vault for the pool
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: UncheckedAccount<'info>,
 
     /// Initialize an account to store oracle observations
     #[account(mut)]
@@ -61,16 +76,16 @@ pub struct CreatePool<'info> {
 
     /// Initialize an account to store if a tick array is initialized.
     #[account(mut)]
-    pub tick_array_bitmap: Account<'info, AccountPlaceholder>,
+    pub tick_array_bitmap: AccountLoader<'info, TickArrayBitmapExtension>,
 
     /// Spl token program or token program 2022
-    pub token_program_0: Account<'info, AccountPlaceholder>,
+    pub token_program_0: Interface<'info, TokenInterface>,
     /// Spl token program or token program 2022
-    pub token_program_1: Account<'info, AccountPlaceholder>,
+    pub token_program_1: Interface<'info, TokenInterface>,
     /// To create a new program account
-    pub system_program: Account<'info, AccountPlaceholder>,
+    pub system_program: Program<'info, System>,
     /// Sysvar for program account
-    pub rent: Account<'info, AccountPlaceholder>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
@@ -91,7 +106,7 @@ pub struct CreateOperationAccount<'info> {
     #[account(mut)]
     pub operation_state: AccountLoader<'info, OperationState>,
 
-    pub system_program: Account<'info, AccountPlaceholder>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -103,7 +118,23 @@ pub struct UpdateOperationAccount<'info> {
     #[account(mut)]
     pub operation_state: AccountLoader<'info, OperationState>,
 
-    pub system_program: Account<'info, AccountPlaceholder>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSupportMintAssociated<'info> {
+    /// Address of the admin, pays to create the support mint account
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The Token-2022 mint to support
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Initialize an account to store the support mint, created by the program
+    #[account(mut)]
+    pub support_mint_associated: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -123,7 +154,7 @@ pub struct InitializeReward<'info> {
 
     // The funder's reward token account
     #[account(mut)]
-    pub funder_token_account: Account<'info, AccountPlaceholder>,
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// For check the reward_funder authority
     pub amm_config: Box<Account<'info, AmmConfig>>,
@@ -136,15 +167,15 @@ pub struct InitializeReward<'info> {
     pub operation_state: AccountLoader<'info, OperationState>,
 
     /// Reward mint
-    pub reward_token_mint: Account<'info, AccountPlaceholder>,
+    pub reward_token_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// A pda, reward vault
+    /// CHECK: A pda, reward vault
     #[account(mut)]
-    pub reward_token_vault: Account<'info, AccountPlaceholder>,
+    pub reward_token_vault: UncheckedAccount<'info>,
 
-    pub reward_token_program: Account<'info, AccountPlaceholder>,
-    pub system_program: Account<'info, AccountPlaceholder>,
-    pub rent: Account<'info, AccountPlaceholder>,
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
@@ -163,20 +194,20 @@ pub struct CollectRemainingRewards<'info> {
     pub reward_funder: Signer<'info>,
     /// The funder's reward token account
     #[account(mut)]
-    pub funder_token_account: Account<'info, AccountPlaceholder>,
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     /// Set reward for this pool
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
     /// Reward vault transfer remaining token to founder token account
-    pub reward_token_vault: Account<'info, AccountPlaceholder>,
+    pub reward_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     /// The mint of reward token vault
-    pub reward_vault_mint: Account<'info, AccountPlaceholder>,
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub reward_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Program<'info, Token>,
     /// Token program 2022
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
 
     /// memo program
-    pub memo_program: Account<'info, AccountPlaceholder>,
+    pub memo_program: Program<'info, Memo>,
 }
 
 #[derive(Accounts)]
@@ -200,9 +231,9 @@ pub struct SetRewardParams<'info> {
     pub operation_state: AccountLoader<'info, OperationState>,
 
     /// Token program
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
     /// Token program 2022
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
@@ -219,31 +250,31 @@ pub struct CollectProtocolFee<'info> {
 
     /// The address that holds pool tokens for token_0
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that holds pool tokens for token_1
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The mint of token vault 0
-    pub vault_0_mint: Account<'info, AccountPlaceholder>,
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The mint of token vault 1
-    pub vault_1_mint: Account<'info, AccountPlaceholder>,
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The address that receives the collected token_0 protocol fees
     #[account(mut)]
-    pub recipient_token_account_0: Account<'info, AccountPlaceholder>,
+    pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that receives the collected token_1 protocol fees
     #[account(mut)]
-    pub recipient_token_account_1: Account<'info, AccountPlaceholder>,
+    pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The SPL program to perform token transfers
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 
     /// The SPL program 2022 to perform token transfers
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
@@ -260,31 +291,31 @@ pub struct CollectFundFee<'info> {
 
     /// The address that holds pool tokens for token_0
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that holds pool tokens for token_1
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The mint of token vault 0
-    pub vault_0_mint: Account<'info, AccountPlaceholder>,
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The mint of token vault 1
-    pub vault_1_mint: Account<'info, AccountPlaceholder>,
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The address that receives the collected token_0 protocol fees
     #[account(mut)]
-    pub recipient_token_account_0: Account<'info, AccountPlaceholder>,
+    pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that receives the collected token_1 protocol fees
     #[account(mut)]
-    pub recipient_token_account_1: Account<'info, AccountPlaceholder>,
+    pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The SPL program to perform token transfers
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 
     /// The SPL program 2022 to perform token transfers
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
@@ -295,72 +326,72 @@ pub struct OpenPosition<'info> {
     pub payer: Signer<'info>,
 
     /// CHECK: Receives the position NFT
-    pub position_nft_owner: Account<'info, AccountPlaceholder>,
+    pub position_nft_owner: UncheckedAccount<'info>,
 
     /// Unique token mint address
     #[account(mut)]
-    pub position_nft_mint: Account<'info, AccountPlaceholder>,
+    pub position_nft_mint: Signer<'info>,
 
-    /// Token account where position NFT will be minted
+    /// CHECK: Token account where position NFT will be minted
     /// This account created in the contract by cpi to avoid large stack variables
     #[account(mut)]
-    pub position_nft_account: Account<'info, AccountPlaceholder>,
+    pub position_nft_account: UncheckedAccount<'info>,
 
     /// To store metaplex metadata
     /// CHECK: Safety check performed inside function body
     #[account(mut)]
-    pub metadata_account: Account<'info, AccountPlaceholder>,
+    pub metadata_account: UncheckedAccount<'info>,
 
     /// Add liquidity for this pool
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    /// Store the information of market marking in range
+    /// CHECK: Store the information of market marking in range, initialized by the program if missing
     #[account(mut)]
-    pub protocol_position: Account<'info, AccountPlaceholder>,
+    pub protocol_position: UncheckedAccount<'info>,
 
     /// CHECK: Account to store data for the position's lower tick
     #[account(mut)]
-    pub tick_array_lower: Account<'info, AccountPlaceholder>,
+    pub tick_array_lower: UncheckedAccount<'info>,
 
     /// CHECK: Account to store data for the position's upper tick
     #[account(mut)]
-    pub tick_array_upper: Account<'info, AccountPlaceholder>,
+    pub tick_array_upper: UncheckedAccount<'info>,
 
-    /// personal position state
+    /// CHECK: personal position state, initialized by the program
     #[account(mut)]
-    pub personal_position: Account<'info, AccountPlaceholder>,
+    pub personal_position: UncheckedAccount<'info>,
 
     /// The token_0 account deposit token to the pool
     #[account(mut)]
-    pub token_account_0: Account<'info, AccountPlaceholder>,
+    pub token_account_0: Box<Account<'info, token::TokenAccount>>,
 
     /// The token_1 account deposit token to the pool
     #[account(mut)]
-    pub token_account_1: Account<'info, AccountPlaceholder>,
+    pub token_account_1: Box<Account<'info, token::TokenAccount>>,
 
     /// The address that holds pool tokens for token_0
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: Box<Account<'info, token::TokenAccount>>,
 
     /// The address that holds pool tokens for token_1
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: Box<Account<'info, token::TokenAccount>>,
 
     /// Sysvar for token mint and ATA creation
-    pub rent: Account<'info, AccountPlaceholder>,
+    pub rent: Sysvar<'info, Rent>,
 
     /// Program to create the position manager state account
-    pub system_program: Account<'info, AccountPlaceholder>,
+    pub system_program: Program<'info, System>,
 
     /// Program to create mint account and mint tokens
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
     /// Program to create an ATA for receiving position NFT
-    pub associated_token_program: Account<'info, AccountPlaceholder>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// Program to create NFT metadata
     /// CHECK: Metadata program address constraint applied
-    pub metadata_program: Account<'info, AccountPlaceholder>,
+    pub metadata_program: Program<'info, Metadata>,
     // remaining account
     // #[account(
     //     seeds = [
@@ -380,79 +411,79 @@ pub struct OpenPositionV2<'info> {
     pub payer: Signer<'info>,
 
     /// CHECK: Receives the position NFT
-    pub position_nft_owner: Account<'info, AccountPlaceholder>,
+    pub position_nft_owner: UncheckedAccount<'info>,
 
     /// Unique token mint address
     #[account(mut)]
-    pub position_nft_mint: Account<'info, AccountPlaceholder>,
+    pub position_nft_mint: Signer<'info>,
 
-    /// Token account where position NFT will be minted
+    /// CHECK: Token account where position NFT will be minted
     #[account(mut)]
-    pub position_nft_account: Account<'info, AccountPlaceholder>,
+    pub position_nft_account: UncheckedAccount<'info>,
 
     /// To store metaplex metadata
     /// CHECK: Safety check performed inside function body
     #[account(mut)]
-    pub metadata_account: Account<'info, AccountPlaceholder>,
+    pub metadata_account: UncheckedAccount<'info>,
 
     /// Add liquidity for this pool
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    /// Store the information of market marking in range
+    /// CHECK: Store the information of market marking in range, initialized by the program if missing
     #[account(mut)]
-    pub protocol_position: Account<'info, AccountPlaceholder>,
+    pub protocol_position: UncheckedAccount<'info>,
 
     /// CHECK: Account to store data for the position's lower tick
     #[account(mut)]
-    pub tick_array_lower: Account<'info, AccountPlaceholder>,
+    pub tick_array_lower: UncheckedAccount<'info>,
 
     /// CHECK: Account to store data for the position's upper tick
     #[account(mut)]
-    pub tick_array_upper: Account<'info, AccountPlaceholder>,
+    pub tick_array_upper: UncheckedAccount<'info>,
 
-    /// personal position state
+    /// CHECK: personal position state, initialized by the program
     #[account(mut)]
-    pub personal_position: Account<'info, AccountPlaceholder>,
+    pub personal_position: UncheckedAccount<'info>,
 
     /// The token_0 account deposit token to the pool
     #[account(mut)]
-    pub token_account_0: Account<'info, AccountPlaceholder>,
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token_1 account deposit token to the pool
     #[account(mut)]
-    pub token_account_1: Account<'info, AccountPlaceholder>,
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that holds pool tokens for token_0
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that holds pool tokens for token_1
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Sysvar for token mint and ATA creation
-    pub rent: Account<'info, AccountPlaceholder>,
+    pub rent: Sysvar<'info, Rent>,
 
     /// Program to create the position manager state account
-    pub system_program: Account<'info, AccountPlaceholder>,
+    pub system_program: Program<'info, System>,
 
     /// Program to create mint account and mint tokens
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
     /// Program to create an ATA for receiving position NFT
-    pub associated_token_program: Account<'info, AccountPlaceholder>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// Program to create NFT metadata
     /// CHECK: Metadata program address constraint applied
-    pub metadata_program: Account<'info, AccountPlaceholder>,
+    pub metadata_program: Program<'info, Metadata>,
     /// Program to create mint account and mint tokens
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
     /// The mint of token vault 0
     #[account(mut)]
-    pub vault_0_mint: Account<'info, AccountPlaceholder>,
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The mint of token vault 1
     #[account(mut)]
-    pub vault_1_mint: Account<'info, AccountPlaceholder>,
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
 }
 
 #[derive(Accounts)]
@@ -463,7 +494,7 @@ pub struct OpenPositionWithToken22Nft<'info> {
     pub payer: Signer<'info>,
 
     /// CHECK: Receives the position NFT
-    pub position_nft_owner: Account<'info, AccountPlaceholder>,
+    pub position_nft_owner: UncheckedAccount<'info>,
 
     /// Unique token mint address, initialize in constract
     #[account(mut)]
@@ -471,66 +502,66 @@ pub struct OpenPositionWithToken22Nft<'info> {
 
     /// CHECK: ATA address where position NFT will be minted, initialize in constract
     #[account(mut)]
-    pub position_nft_account: Account<'info, AccountPlaceholder>,
+    pub position_nft_account: UncheckedAccount<'info>,
 
     /// Add liquidity for this pool
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    /// Store the information of market marking in range
+    /// CHECK: Store the information of market marking in range, initialized by the program if missing
     #[account(mut)]
-    pub protocol_position: Account<'info, AccountPlaceholder>,
+    pub protocol_position: UncheckedAccount<'info>,
 
     /// CHECK: Account to store data for the position's lower tick
     #[account(mut)]
-    pub tick_array_lower: Account<'info, AccountPlaceholder>,
+    pub tick_array_lower: UncheckedAccount<'info>,
 
     /// CHECK: Account to store data for the position's upper tick
     #[account(mut)]
-    pub tick_array_upper: Account<'info, AccountPlaceholder>,
+    pub tick_array_upper: UncheckedAccount<'info>,
 
-    /// personal position state
+    /// CHECK: personal position state, initialized by the program
     #[account(mut)]
-    pub personal_position: Account<'info, AccountPlaceholder>,
+    pub personal_position: UncheckedAccount<'info>,
 
     /// The token_0 account deposit token to the pool
     #[account(mut)]
-    pub token_account_0: Account<'info, AccountPlaceholder>,
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token_1 account deposit token to the pool
     #[account(mut)]
-    pub token_account_1: Account<'info, AccountPlaceholder>,
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that holds pool tokens for token_0
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that holds pool tokens for token_1
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Sysvar for token mint and ATA creation
-    pub rent: Account<'info, AccountPlaceholder>,
+    pub rent: Sysvar<'info, Rent>,
 
     /// Program to create the position manager state account
-    pub system_program: Account<'info, AccountPlaceholder>,
+    pub system_program: Program<'info, System>,
 
     /// Program to transfer for token account
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 
     /// Program to create an ATA for receiving position NFT
-    pub associated_token_program: Account<'info, AccountPlaceholder>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// Program to create NFT mint/token account and transfer for token22 account
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
 
     /// The mint of token vault 0
     #[account(mut)]
-    pub vault_0_mint: Account<'info, AccountPlaceholder>,
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The mint of token vault 1
     #[account(mut)]
-    pub vault_1_mint: Account<'info, AccountPlaceholder>,
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
 }
 
 #[derive(Accounts)]
@@ -541,20 +572,20 @@ pub struct ClosePosition<'info> {
 
     /// Mint address bound to the personal position.
     #[account(mut)]
-    pub position_nft_mint: Account<'info, AccountPlaceholder>,
+    pub position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// User token account where position NFT be minted to
     #[account(mut)]
-    pub position_nft_account: Account<'info, AccountPlaceholder>,
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub personal_position: Account<'info, AccountPlaceholder>,
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
 
     /// System program to close the position state account
-    pub system_program: Account<'info, AccountPlaceholder>,
+    pub system_program: Program<'info, System>,
 
     /// Token/Token2022 program to close token/mint account
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -563,44 +594,44 @@ pub struct IncreaseLiquidity<'info> {
     pub nft_owner: Signer<'info>,
 
     /// The token account for nft
-    pub nft_account: Account<'info, AccountPlaceholder>,
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
     #[account(mut)]
-    pub protocol_position: Account<'info, AccountPlaceholder>,
+    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
 
     /// Increase liquidity for this position
     #[account(mut)]
-    pub personal_position: Account<'info, AccountPlaceholder>,
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
 
     /// Stores init state for the lower tick
     #[account(mut)]
-    pub tick_array_lower: Account<'info, AccountPlaceholder>,
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
 
     /// Stores init state for the upper tick
     #[account(mut)]
-    pub tick_array_upper: Account<'info, AccountPlaceholder>,
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
 
     /// The payer's token account for token_0
     #[account(mut)]
-    pub token_account_0: Account<'info, AccountPlaceholder>,
+    pub token_account_0: Box<Account<'info, token::TokenAccount>>,
 
     /// The token account spending token_1 to mint the position
     #[account(mut)]
-    pub token_account_1: Account<'info, AccountPlaceholder>,
+    pub token_account_1: Box<Account<'info, token::TokenAccount>>,
 
     /// The address that holds pool tokens for token_0
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: Box<Account<'info, token::TokenAccount>>,
 
     /// The address that holds pool tokens for token_1
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: Box<Account<'info, token::TokenAccount>>,
 
     /// Program to create mint account and mint tokens
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
@@ -609,55 +640,55 @@ pub struct IncreaseLiquidityV2<'info> {
     pub nft_owner: Signer<'info>,
 
     /// The token account for nft
-    pub nft_account: Account<'info, AccountPlaceholder>,
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
     #[account(mut)]
-    pub protocol_position: Account<'info, AccountPlaceholder>,
+    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
 
     /// Increase liquidity for this position
     #[account(mut)]
-    pub personal_position: Account<'info, AccountPlaceholder>,
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
 
     /// Stores init state for the lower tick
     #[account(mut)]
-    pub tick_array_lower: Account<'info, AccountPlaceholder>,
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
 
     /// Stores init state for the upper tick
     #[account(mut)]
-    pub tick_array_upper: Account<'info, AccountPlaceholder>,
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
 
     /// The payer's token account for token_0
     #[account(mut)]
-    pub token_account_0: Account<'info, AccountPlaceholder>,
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token account spending token_1 to mint the position
     #[account(mut)]
-    pub token_account_1: Account<'info, AccountPlaceholder>,
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that holds pool tokens for token_0
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The address that holds pool tokens for token_1
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Program to create mint account and mint tokens
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 
     /// Token program 2022
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
 
     /// The mint of token vault 0
     #[account(mut)]
-    pub vault_0_mint: Account<'info, AccountPlaceholder>,
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The mint of token vault 1
     #[account(mut)]
-    pub vault_1_mint: Account<'info, AccountPlaceholder>,
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
 }
 
 #[derive(Accounts)]
@@ -666,44 +697,44 @@ pub struct DecreaseLiquidity<'info> {
     pub nft_owner: Signer<'info>,
 
     /// The token account for the tokenized position
-    pub nft_account: Account<'info, AccountPlaceholder>,
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Decrease liquidity for this position
     #[account(mut)]
-    pub personal_position: Account<'info, AccountPlaceholder>,
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
 
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
     #[account(mut)]
-    pub protocol_position: Account<'info, AccountPlaceholder>,
+    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
 
     /// Token_0 vault
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: Box<Account<'info, token::TokenAccount>>,
 
     /// Token_1 vault
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: Box<Account<'info, token::TokenAccount>>,
 
     /// Stores init state for the lower tick
     #[account(mut)]
-    pub tick_array_lower: Account<'info, AccountPlaceholder>,
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
 
     /// Stores init state for the upper tick
     #[account(mut)]
-    pub tick_array_upper: Account<'info, AccountPlaceholder>,
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
 
     /// The destination token account for receive amount_0
     #[account(mut)]
-    pub recipient_token_account_0: Account<'info, AccountPlaceholder>,
+    pub recipient_token_account_0: Box<Account<'info, token::TokenAccount>>,
 
     /// The destination token account for receive amount_1
     #[account(mut)]
-    pub recipient_token_account_1: Account<'info, AccountPlaceholder>,
+    pub recipient_token_account_1: Box<Account<'info, token::TokenAccount>>,
 
     /// SPL program to transfer out tokens
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
@@ -712,59 +743,59 @@ pub struct DecreaseLiquidityV2<'info> {
     pub nft_owner: Signer<'info>,
 
     /// The token account for the tokenized position
-    pub nft_account: Account<'info, AccountPlaceholder>,
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Decrease liquidity for this position
     #[account(mut)]
-    pub personal_position: Account<'info, AccountPlaceholder>,
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
 
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
     #[account(mut)]
-    pub protocol_position: Account<'info, AccountPlaceholder>,
+    pub protocol_position: Box<Account<'info, ProtocolPositionState>>,
 
     /// Token_0 vault
     #[account(mut)]
-    pub token_vault_0: Account<'info, AccountPlaceholder>,
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Token_1 vault
     #[account(mut)]
-    pub token_vault_1: Account<'info, AccountPlaceholder>,
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Stores init state for the lower tick
     #[account(mut)]
-    pub tick_array_lower: Account<'info, AccountPlaceholder>,
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
 
     /// Stores init state for the upper tick
     #[account(mut)]
-    pub tick_array_upper: Account<'info, AccountPlaceholder>,
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
 
     /// The destination token account for receive amount_0
     #[account(mut)]
-    pub recipient_token_account_0: Account<'info, AccountPlaceholder>,
+    pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The destination token account for receive amount_1
     #[account(mut)]
-    pub recipient_token_account_1: Account<'info, AccountPlaceholder>,
+    pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// SPL program to transfer out tokens
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 
     /// Token program 2022
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
 
     /// memo program
     /// CHECK:
-    pub memo_program: Account<'info, AccountPlaceholder>,
+    pub memo_program: Program<'info, Memo>,
 
     /// The mint of token vault 0
     #[account(mut)]
-    pub vault_0_mint: Account<'info, AccountPlaceholder>,
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The mint of token vault 1
     #[account(mut)]
-    pub vault_1_mint: Account<'info, AccountPlaceholder>,
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
 }
 
 #[derive(Accounts)]
@@ -781,29 +812,29 @@ pub struct SwapSingle<'info> {
 
     /// The user token account for input token
     #[account(mut)]
-    pub input_token_account: Account<'info, AccountPlaceholder>,
+    pub input_token_account: Box<Account<'info, token::TokenAccount>>,
 
     /// The user token account for output token
     #[account(mut)]
-    pub output_token_account: Account<'info, AccountPlaceholder>,
+    pub output_token_account: Box<Account<'info, token::TokenAccount>>,
 
     /// The vault token account for input token
     #[account(mut)]
-    pub input_vault: Account<'info, AccountPlaceholder>,
+    pub input_vault: Box<Account<'info, token::TokenAccount>>,
 
     /// The vault token account for output token
     #[account(mut)]
-    pub output_vault: Account<'info, AccountPlaceholder>,
+    pub output_vault: Box<Account<'info, token::TokenAccount>>,
 
     /// The program account for the most recent oracle observation
     #[account(mut)]
     pub observation_state: AccountLoader<'info, ObservationState>,
 
     /// SPL program for token transfers
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 
     #[account(mut)]
-    pub tick_array: Account<'info, AccountPlaceholder>,
+    pub tick_array: AccountLoader<'info, TickArrayState>,
 }
 
 #[derive(Accounts)]
@@ -820,40 +851,40 @@ pub struct SwapSingleV2<'info> {
 
     /// The user token account for input token
     #[account(mut)]
-    pub input_token_account: Account<'info, AccountPlaceholder>,
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The user token account for output token
     #[account(mut)]
-    pub output_token_account: Account<'info, AccountPlaceholder>,
+    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The vault token account for input token
     #[account(mut)]
-    pub input_vault: Account<'info, AccountPlaceholder>,
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The vault token account for output token
     #[account(mut)]
-    pub output_vault: Account<'info, AccountPlaceholder>,
+    pub output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The program account for the most recent oracle observation
     #[account(mut)]
     pub observation_state: AccountLoader<'info, ObservationState>,
 
     /// SPL program for token transfers
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 
     /// SPL program 2022 for token transfers
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
 
     /// CHECK:
-    pub memo_program: Account<'info, AccountPlaceholder>,
+    pub memo_program: Program<'info, Memo>,
 
     /// The mint of token vault 0
     #[account(mut)]
-    pub input_vault_mint: Account<'info, AccountPlaceholder>,
+    pub input_vault_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The mint of token vault 1
     #[account(mut)]
-    pub output_vault_mint: Account<'info, AccountPlaceholder>,
+    pub output_vault_mint: Box<InterfaceAccount<'info, Mint>>,
 }
 
 #[derive(Accounts)]
@@ -863,18 +894,18 @@ pub struct SwapRouterBaseIn<'info> {
 
     /// The token account that pays input tokens for the swap
     #[account(mut)]
-    pub input_token_account: Account<'info, AccountPlaceholder>,
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The mint of input token
     #[account(mut)]
-    pub input_token_mint: Account<'info, AccountPlaceholder>,
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// SPL program for token transfers
-    pub token_program: Account<'info, AccountPlaceholder>,
+    pub token_program: Program<'info, Token>,
 
     /// SPL program 2022 for token transfers
-    pub token_program_2022: Account<'info, AccountPlaceholder>,
+    pub token_program_2022: Program<'info, Token2022>,
 
     /// CHECK:
-    pub memo_program: Account<'info, AccountPlaceholder>,
+    pub memo_program: Program<'info, Memo>,
 }
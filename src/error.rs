@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+
+/// Errors that can be returned by the Raydium CLMM program, in the same order as on chain
+/// so that the numeric codes (starting at 6000) match.
+#[error_code]
+#[derive(PartialEq, Eq)]
+pub enum ErrorCode {
+    #[msg("LOK")]
+    LOK,
+    #[msg("Not approved")]
+    NotApproved,
+    #[msg("invalid update amm config flag")]
+    InvalidUpdateConfigFlag,
+    #[msg("Account lack")]
+    AccountLack,
+    #[msg("Remove liquitity, collect fees owed and reward then you can close position account")]
+    ClosePositionErr,
+
+    #[msg("Minting amount should be greater than 0")]
+    ZeroMintAmount,
+
+    #[msg("Tick out of range")]
+    InvaildTickIndex,
+    #[msg("The lower tick must be below the upper tick")]
+    TickInvaildOrder,
+    #[msg("The tick must be greater, or equal to the minimum tick(-443636)")]
+    TickLowerOverflow,
+    #[msg("The tick must be lesser than, or equal to the maximum tick(443636)")]
+    TickUpperOverflow,
+    #[msg("tick % tick_spacing must be zero")]
+    TickAndSpacingNotMatch,
+    #[msg("Invaild tick array account")]
+    InvalidTickArray,
+    #[msg("Invaild tick array boundary")]
+    InvalidTickArrayBoundary,
+
+    #[msg("Square root price limit overflow")]
+    SqrtPriceLimitOverflow,
+    // second inequality must be < because the price can never reach the price at the max tick
+    #[msg("sqrt_price_x64 out of range")]
+    SqrtPriceX64,
+
+    #[msg("Liquidity sub delta L must be smaller than before")]
+    LiquiditySubValueErr,
+    #[msg("Liquidity add delta L must be greater, or equal to before")]
+    LiquidityAddValueErr,
+    #[msg("Invaild liquidity when update position")]
+    InvaildLiquidity,
+    #[msg("Both token amount must not be zero while supply liquidity")]
+    ForbidBothZeroForSupplyLiquidity,
+    #[msg("Liquidity insufficient")]
+    LiquidityInsufficient,
+
+    #[msg("Transaction too old")]
+    TransactionTooOld,
+    #[msg("Price slippage check")]
+    PriceSlippageCheck,
+    #[msg("Too little output received")]
+    TooLittleOutputReceived,
+    #[msg("Too much input paid")]
+    TooMuchInputPaid,
+    #[msg("Swap special amount can not be zero")]
+    ZeroAmountSpecified,
+    #[msg("Input pool vault is invalid")]
+    InvalidInputPoolVault,
+    #[msg("Swap input or output amount is too small")]
+    TooSmallInputOrOutputAmount,
+    #[msg("Not enought tick array account")]
+    NotEnoughTickArrayAccount,
+    #[msg("Invaild first tick array account")]
+    InvalidFirstTickArrayAccount,
+
+    #[msg("Invalid reward index")]
+    InvalidRewardIndex,
+    #[msg("The init reward token reach to the max")]
+    FullRewardInfo,
+    #[msg("The init reward token already in use")]
+    RewardTokenAlreadyInUse,
+    #[msg("The reward tokens must contain one of pool vault mint except the last reward")]
+    ExceptPoolVaultMint,
+    #[msg("Invalid reward init param")]
+    InvalidRewardInitParam,
+    #[msg("Invalid collect reward desired amount")]
+    InvalidRewardDesiredAmount,
+    #[msg("Invalid collect reward input account number")]
+    InvalidRewardInputAccountNumber,
+    #[msg("Invalid reward period")]
+    InvalidRewardPeriod,
+    #[msg(
+        "Modification of emissiones is allowed within 72 hours from the end of the previous cycle"
+    )]
+    NotApproveUpdateRewardEmissiones,
+    #[msg("uninitialized reward info")]
+    UnInitializedRewardInfo,
+
+    #[msg("Not support token_2022 mint extension")]
+    NotSupportMint,
+    #[msg("Missing tickarray bitmap extension account")]
+    MissingTickArrayBitmapExtensionAccount,
+    #[msg("Insufficient liquidity for this direction")]
+    InsufficientLiquidityForDirection,
+    #[msg("Max token overflow")]
+    MaxTokenOverflow,
+    #[msg("calculate overflow")]
+    CalculateOverflow,
+    #[msg("TransferFee calculate not match")]
+    TransferFeeCalculateNotMatch,
+}
+
+impl ErrorCode {
+    /// All the variants, indexed by `code - ERROR_CODE_OFFSET`
+    pub const ALL: [ErrorCode; 45] = [
+        ErrorCode::LOK,
+        ErrorCode::NotApproved,
+        ErrorCode::InvalidUpdateConfigFlag,
+        ErrorCode::AccountLack,
+        ErrorCode::ClosePositionErr,
+        ErrorCode::ZeroMintAmount,
+        ErrorCode::InvaildTickIndex,
+        ErrorCode::TickInvaildOrder,
+        ErrorCode::TickLowerOverflow,
+        ErrorCode::TickUpperOverflow,
+        ErrorCode::TickAndSpacingNotMatch,
+        ErrorCode::InvalidTickArray,
+        ErrorCode::InvalidTickArrayBoundary,
+        ErrorCode::SqrtPriceLimitOverflow,
+        ErrorCode::SqrtPriceX64,
+        ErrorCode::LiquiditySubValueErr,
+        ErrorCode::LiquidityAddValueErr,
+        ErrorCode::InvaildLiquidity,
+        ErrorCode::ForbidBothZeroForSupplyLiquidity,
+        ErrorCode::LiquidityInsufficient,
+        ErrorCode::TransactionTooOld,
+        ErrorCode::PriceSlippageCheck,
+        ErrorCode::TooLittleOutputReceived,
+        ErrorCode::TooMuchInputPaid,
+        ErrorCode::ZeroAmountSpecified,
+        ErrorCode::InvalidInputPoolVault,
+        ErrorCode::TooSmallInputOrOutputAmount,
+        ErrorCode::NotEnoughTickArrayAccount,
+        ErrorCode::InvalidFirstTickArrayAccount,
+        ErrorCode::InvalidRewardIndex,
+        ErrorCode::FullRewardInfo,
+        ErrorCode::RewardTokenAlreadyInUse,
+        ErrorCode::ExceptPoolVaultMint,
+        ErrorCode::InvalidRewardInitParam,
+        ErrorCode::InvalidRewardDesiredAmount,
+        ErrorCode::InvalidRewardInputAccountNumber,
+        ErrorCode::InvalidRewardPeriod,
+        ErrorCode::NotApproveUpdateRewardEmissiones,
+        ErrorCode::UnInitializedRewardInfo,
+        ErrorCode::NotSupportMint,
+        ErrorCode::MissingTickArrayBitmapExtensionAccount,
+        ErrorCode::InsufficientLiquidityForDirection,
+        ErrorCode::MaxTokenOverflow,
+        ErrorCode::CalculateOverflow,
+        ErrorCode::TransferFeeCalculateNotMatch,
+    ];
+
+    /// Lookup the error from the custom error number returned by the program
+    pub fn from_u32(code: u32) -> Option<ErrorCode> {
+        let index = code.checked_sub(anchor_lang::error::ERROR_CODE_OFFSET)?;
+        ErrorCode::ALL.get(index as usize).copied()
+    }
+
+    /// Lookup the error from an anchor error, e.g. the one returned by a failed CPI
+    pub fn from_anchor_error(error: &anchor_lang::error::Error) -> Option<ErrorCode> {
+        match error {
+            anchor_lang::error::Error::AnchorError(error) => {
+                ErrorCode::from_u32(error.error_code_number)
+            }
+            anchor_lang::error::Error::ProgramError(error) => match error.program_error {
+                ProgramError::Custom(code) => ErrorCode::from_u32(code),
+                _ => None,
+            },
+        }
+    }
+}
@@ -1,11 +1,19 @@
 #![allow(unused)]
+#![allow(clippy::too_many_arguments)]
 
 use anchor_lang::prelude::*;
 
 pub mod account;
 use account::*;
+pub mod client;
 pub mod context;
 use context::*;
+pub mod error;
+pub mod event;
+pub mod libraries;
+pub mod pda;
+pub mod swap;
+pub mod token;
 
 #[cfg(feature = "devnet")]
 declare_id!("devi51mZmdwUJGU9hjN27vEz64Gps7uUefqxg27EAtH");
@@ -110,6 +118,17 @@ pub mod raydium_clmm {
         Ok(())
     }
 
+    /// Create a support mint associated account for a Token-2022 mint, which allows the mint
+    /// to create pools and be used as reward even if it has not supported extensions
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn create_support_mint_associated(ctx: Context<CreateSupportMintAssociated>) -> Result<()> {
+        Ok(())
+    }
+
     /// Transfer reward owner
     ///
     /// # Arguments
@@ -445,6 +464,10 @@ pub mod raydium_clmm {
     /// * `amount_in` - Token amount to be swapped in
     /// * `amount_out_minimum` - Panic if output amount is below minimum amount. For slippage.
     ///
+    /// The hops are passed in the remaining accounts, for every hop: amm_config, pool_state,
+    /// output_token_account, input_vault, output_vault, output_token_mint, observation_state,
+    /// then the optional tick array bitmap extension and the tick arrays.
+    ///
     pub fn swap_router_base_in<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, SwapRouterBaseIn<'info>>,
         amount_in: u64,
@@ -452,4 +475,4 @@ pub mod raydium_clmm {
     ) -> Result<()> {
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,24 @@
+//! 128, 256, 512 and 1024 bit numbers
+//! U128 is more efficient that u128
+//! https://github.com/solana-labs/solana/issues/19549
+#![allow(clippy::assign_op_pattern)]
+#![allow(clippy::manual_div_ceil)]
+#![allow(clippy::reversed_empty_ranges)]
+
+use uint::construct_uint;
+
+construct_uint! {
+    pub struct U128(2);
+}
+
+construct_uint! {
+    pub struct U256(4);
+}
+
+construct_uint! {
+    pub struct U512(8);
+}
+
+construct_uint! {
+    pub struct U1024(16);
+}
@@ -0,0 +1,7 @@
+//! A library for handling Q64.64 fixed point numbers
+//! Used in sqrt_price_math.rs and liquidity_math.rs
+
+/// The number of fractional bits of a Q64.64 number
+pub const RESOLUTION: u8 = 64;
+/// 2^64, i.e. 1.0 as a Q64.64 number
+pub const Q64: u128 = (u64::MAX as u128) + 1; // 2^64
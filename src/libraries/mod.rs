@@ -0,0 +1,9 @@
+pub mod big_num;
+pub mod fixed_point_64;
+pub mod full_math;
+pub mod liquidity_math;
+pub mod sqrt_price_math;
+pub mod swap_math;
+pub mod tick_array_bit_map;
+pub mod tick_math;
+pub mod unsafe_math;
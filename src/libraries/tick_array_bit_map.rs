@@ -0,0 +1,141 @@
+//! Helpers to navigate the tick array bitmap stored in `PoolState::tick_array_bitmap`.
+//! Every bit represents one tick array, the bitmap covers 512 tick arrays on each side of tick 0.
+
+use super::big_num::U1024;
+use crate::account::{TickArrayState, TickState, TICK_ARRAY_SIZE};
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const TICK_ARRAY_BITMAP_SIZE: i32 = 512;
+
+pub type TickArryBitmap = [u64; 8];
+
+/// The tick boundary that one bitmap (512 tick arrays) can represent
+pub fn max_tick_in_tickarray_bitmap(tick_spacing: u16) -> i32 {
+    i32::from(tick_spacing) * TICK_ARRAY_SIZE * TICK_ARRAY_BITMAP_SIZE
+}
+
+/// Get the `[min, max)` tick range covered by the bitmap which `tick_array_start_index` belongs to
+pub fn get_bitmap_tick_boundary(tick_array_start_index: i32, tick_spacing: u16) -> (i32, i32) {
+    let ticks_in_one_bitmap: i32 = max_tick_in_tickarray_bitmap(tick_spacing);
+    let mut m = tick_array_start_index.abs() / ticks_in_one_bitmap;
+    if tick_array_start_index < 0 && tick_array_start_index.abs() % ticks_in_one_bitmap != 0 {
+        m += 1;
+    }
+    let min_value: i32 = ticks_in_one_bitmap * m;
+    if tick_array_start_index < 0 {
+        (-min_value, -min_value + ticks_in_one_bitmap)
+    } else {
+        (min_value, min_value + ticks_in_one_bitmap)
+    }
+}
+
+pub fn most_significant_bit(x: U1024) -> Option<u16> {
+    if x.is_zero() {
+        None
+    } else {
+        Some(u16::try_from(x.leading_zeros()).unwrap())
+    }
+}
+
+pub fn least_significant_bit(x: U1024) -> Option<u16> {
+    if x.is_zero() {
+        None
+    } else {
+        Some(u16::try_from(x.trailing_zeros()).unwrap())
+    }
+}
+
+/// Given a tick, calculate whether the tickarray it belongs to has been initialized.
+pub fn check_current_tick_array_is_initialized(
+    bit_map: U1024,
+    tick_current: i32,
+    tick_spacing: u16,
+) -> Result<(bool, i32)> {
+    if TickState::check_is_out_of_boundary(tick_current) {
+        return err!(ErrorCode::InvaildTickIndex);
+    }
+    let multiplier = TickArrayState::tick_count(tick_spacing);
+    let mut compressed = tick_current / multiplier + TICK_ARRAY_BITMAP_SIZE;
+    if tick_current < 0 && tick_current % multiplier != 0 {
+        // round towards negative infinity
+        compressed -= 1;
+    }
+    let bit_pos = compressed.abs();
+    // set current bit
+    let mask = U1024::one() << bit_pos;
+    let masked = bit_map & mask;
+    // check the current bit whether initialized
+    let initialized = masked != U1024::default();
+    Ok((
+        initialized,
+        (compressed - TICK_ARRAY_BITMAP_SIZE) * multiplier,
+    ))
+}
+
+/// Search the next initialized tick array in the default bitmap of the pool,
+/// starting next to `last_tick_array_start_index` in the swap direction.
+///
+/// Returns `(true, start_index)` if found, otherwise `(false, boundary)` where boundary is
+/// the last tick array start index the bitmap can represent in that direction.
+pub fn next_initialized_tick_array_start_index(
+    bit_map: U1024,
+    last_tick_array_start_index: i32,
+    tick_spacing: u16,
+    zero_for_one: bool,
+) -> (bool, i32) {
+    assert!(TickArrayState::check_is_valid_start_index(
+        last_tick_array_start_index,
+        tick_spacing
+    ));
+    let tick_boundary = max_tick_in_tickarray_bitmap(tick_spacing);
+    let next_tick_array_start_index = if zero_for_one {
+        last_tick_array_start_index - TickArrayState::tick_count(tick_spacing)
+    } else {
+        last_tick_array_start_index + TickArrayState::tick_count(tick_spacing)
+    };
+
+    if next_tick_array_start_index < -tick_boundary || next_tick_array_start_index >= tick_boundary
+    {
+        return (false, last_tick_array_start_index);
+    }
+
+    let multiplier = TickArrayState::tick_count(tick_spacing);
+    let mut compressed = next_tick_array_start_index / multiplier + TICK_ARRAY_BITMAP_SIZE;
+    if next_tick_array_start_index < 0 && next_tick_array_start_index % multiplier != 0 {
+        // round towards negative infinity
+        compressed -= 1;
+    }
+    let bit_pos = compressed.abs();
+
+    if zero_for_one {
+        // tick from upper to lower
+        // find from highter bits to lower bits
+        let offset_bit_map = bit_map << (1024 - bit_pos - 1);
+        match most_significant_bit(offset_bit_map) {
+            Some(next_bit) => {
+                let next_array_start_index =
+                    (bit_pos - i32::from(next_bit) - TICK_ARRAY_BITMAP_SIZE) * multiplier;
+                (true, next_array_start_index)
+            }
+            // not found til to the end
+            None => (false, -tick_boundary),
+        }
+    } else {
+        // tick from lower to upper
+        // find from lower bits to highter bits
+        let offset_bit_map = bit_map >> bit_pos;
+        match least_significant_bit(offset_bit_map) {
+            Some(next_bit) => {
+                let next_array_start_index =
+                    (bit_pos + i32::from(next_bit) - TICK_ARRAY_BITMAP_SIZE) * multiplier;
+                (true, next_array_start_index)
+            }
+            // not found til to the end
+            None => (
+                false,
+                tick_boundary - TickArrayState::tick_count(tick_spacing),
+            ),
+        }
+    }
+}
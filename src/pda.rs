@@ -0,0 +1,116 @@
+//! Program derived addresses of the Raydium CLMM accounts.
+//!
+//! All addresses are derived from `crate::id()`, so they follow the `devnet` feature.
+
+use anchor_lang::prelude::*;
+
+use crate::account::*;
+
+/// Address of the `AmmConfig` with the given `index`
+pub fn find_amm_config_address(index: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[AMM_CONFIG_SEED.as_bytes(), &index.to_be_bytes()],
+        &crate::id(),
+    )
+}
+
+/// Address of the `PoolState` of a token pair, `token_mint_0` must be smaller than `token_mint_1`
+pub fn find_pool_address(
+    amm_config: &Pubkey,
+    token_mint_0: &Pubkey,
+    token_mint_1: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            POOL_SEED.as_bytes(),
+            amm_config.as_ref(),
+            token_mint_0.as_ref(),
+            token_mint_1.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+/// Address of the pool's token vault of `token_mint`
+pub fn find_pool_vault_address(pool: &Pubkey, token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            POOL_VAULT_SEED.as_bytes(),
+            pool.as_ref(),
+            token_mint.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+/// Address of the pool's `ObservationState`
+pub fn find_observation_address(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OBSERVATION_SEED.as_bytes(), pool.as_ref()], &crate::id())
+}
+
+/// Address of the pool's `TickArrayState` starting at `start_tick_index`
+pub fn find_tick_array_address(pool: &Pubkey, start_tick_index: i32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            TICK_ARRAY_SEED.as_bytes(),
+            pool.as_ref(),
+            &start_tick_index.to_be_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+/// Address of the pool's `TickArrayBitmapExtension`
+pub fn find_bitmap_extension_address(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool.as_ref()],
+        &crate::id(),
+    )
+}
+
+/// Address of the `ProtocolPositionState` of the pool in `[tick_lower_index, tick_upper_index)`
+pub fn find_protocol_position_address(
+    pool: &Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            POSITION_SEED.as_bytes(),
+            pool.as_ref(),
+            &tick_lower_index.to_be_bytes(),
+            &tick_upper_index.to_be_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+/// Address of the `PersonalPositionState` bound to the position NFT `nft_mint`
+pub fn find_personal_position_address(nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POSITION_SEED.as_bytes(), nft_mint.as_ref()], &crate::id())
+}
+
+/// Address of the pool's reward vault of `reward_mint`
+pub fn find_reward_vault_address(pool: &Pubkey, reward_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            POOL_REWARD_VAULT_SEED.as_bytes(),
+            pool.as_ref(),
+            reward_mint.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+/// Address of the program's `OperationState`
+pub fn find_operation_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OPERATION_SEED.as_bytes()], &crate::id())
+}
+
+/// Address of the `SupportMintAssociated` of `token_mint`
+pub fn find_support_mint_associated_address(token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SUPPORT_MINT_SEED.as_bytes(), token_mint.as_ref()],
+        &crate::id(),
+    )
+}
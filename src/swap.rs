@@ -0,0 +1,585 @@
+//! An offline engine reproducing the program's `swap_internal` over decoded accounts,
+//! used to quote swaps and to know which tick arrays a swap will walk through.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee;
+
+use crate::account::*;
+use crate::error::ErrorCode;
+use crate::libraries::big_num::{U128, U512};
+use crate::libraries::{fixed_point_64, full_math::MulDiv, liquidity_math, swap_math, tick_math};
+use crate::token;
+
+/// The outcome of a simulated swap in a single pool
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapResult {
+    /// Amount of input token paid into the pool, fee included
+    pub amount_in: u64,
+    /// Amount of output token paid out of the pool
+    pub amount_out: u64,
+    /// Trade fee charged on the input token, protocol and fund fees included
+    pub fee_amount: u64,
+    /// Part of `fee_amount` accrued to the protocol
+    pub protocol_fee: u64,
+    /// Part of `fee_amount` accrued to the fund
+    pub fund_fee: u64,
+    /// Pool sqrt price after the swap
+    pub sqrt_price_x64: u128,
+    /// Pool current tick after the swap
+    pub tick_current: i32,
+    /// Pool in range liquidity after the swap
+    pub liquidity: u128,
+    /// Start indexes of the tick arrays traversed by the swap, in the order the program needs them
+    pub tick_array_start_indexes: Vec<i32>,
+}
+
+impl SwapResult {
+    /// The tick arrays the swap walks through, `pool_state` is the pool before the swap
+    pub fn tick_arrays(&self, pool_state: &PoolState) -> SwapTickArrays {
+        pool_state.swap_tick_arrays(self.tick_array_start_indexes.clone())
+    }
+}
+
+// the top level state of the swap, the results of which are recorded in storage at the end
+#[derive(Debug)]
+struct SwapState {
+    // the amount remaining to be swapped in/out of the input/output asset
+    amount_specified_remaining: u64,
+    // the amount already swapped out/in of the output/input asset
+    amount_calculated: u64,
+    // current sqrt(price)
+    sqrt_price_x64: u128,
+    // the tick associated with the current price
+    tick: i32,
+    // the global fee growth of the input token
+    fee_growth_global_x64: u128,
+    // amount of input token paid as fee
+    fee_amount: u64,
+    // amount of input token paid as protocol fee
+    protocol_fee: u64,
+    // amount of input token paid as fund fee
+    fund_fee: u64,
+    // the current liquidity in range
+    liquidity: u128,
+}
+
+#[derive(Default)]
+struct StepComputations {
+    // the price at the beginning of the step
+    sqrt_price_start_x64: u128,
+    // the next tick to swap to from the current tick in the swap direction
+    tick_next: i32,
+    // whether tick_next is initialized or not
+    initialized: bool,
+    // sqrt(price) for the next tick (1/0)
+    sqrt_price_next_x64: u128,
+    // how much is being swapped in in this step
+    amount_in: u64,
+    // how much is being swapped out
+    amount_out: u64,
+    // how much fee is being paid in
+    fee_amount: u64,
+}
+
+/// Simulate a swap against decoded pool accounts, reproducing the program's `swap_internal`.
+///
+/// `tick_arrays` may contain the tick arrays of the pool in any order, the ones the swap walks through
+/// are looked up by start index and reported in `SwapResult::tick_array_start_indexes`.
+/// `tickarray_bitmap_extension` is required once the swap leaves the range of `PoolState::tick_array_bitmap`.
+///
+/// `sqrt_price_limit_x64` of zero means no limit, as for `swap_v2`.
+pub fn swap_internal(
+    amm_config: &AmmConfig,
+    pool_state: &PoolState,
+    tick_arrays: &[TickArrayState],
+    tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
+    amount_specified: u64,
+    sqrt_price_limit_x64: u128,
+    zero_for_one: bool,
+    is_base_input: bool,
+) -> Result<SwapResult> {
+    require!(amount_specified != 0, ErrorCode::ZeroAmountSpecified);
+    if !pool_state.is_swap_enabled() {
+        return err!(ErrorCode::NotApproved);
+    }
+    let sqrt_price_limit_x64 = if sqrt_price_limit_x64 == 0 {
+        if zero_for_one {
+            tick_math::MIN_SQRT_PRICE_X64 + 1
+        } else {
+            tick_math::MAX_SQRT_PRICE_X64 - 1
+        }
+    } else {
+        sqrt_price_limit_x64
+    };
+    require!(
+        if zero_for_one {
+            sqrt_price_limit_x64 < pool_state.sqrt_price_x64
+                && sqrt_price_limit_x64 > tick_math::MIN_SQRT_PRICE_X64
+        } else {
+            sqrt_price_limit_x64 > pool_state.sqrt_price_x64
+                && sqrt_price_limit_x64 < tick_math::MAX_SQRT_PRICE_X64
+        },
+        ErrorCode::SqrtPriceLimitOverflow
+    );
+
+    let tick_spacing = pool_state.tick_spacing;
+    let find_tick_array = |start_index: i32| -> Result<&TickArrayState> {
+        tick_arrays
+            .iter()
+            .find(|tick_array| tick_array.start_tick_index == start_index)
+            .ok_or_else(|| ErrorCode::NotEnoughTickArrayAccount.into())
+    };
+
+    let mut state = SwapState {
+        amount_specified_remaining: amount_specified,
+        amount_calculated: 0,
+        sqrt_price_x64: pool_state.sqrt_price_x64,
+        tick: pool_state.tick_current,
+        fee_growth_global_x64: if zero_for_one {
+            pool_state.fee_growth_global_0_x64
+        } else {
+            pool_state.fee_growth_global_1_x64
+        },
+        fee_amount: 0,
+        protocol_fee: 0,
+        fund_fee: 0,
+        liquidity: pool_state.liquidity,
+    };
+    let mut total_fee_amount: u64 = 0;
+
+    let (mut is_match_pool_current_tick_array, first_vaild_tick_array_start_index) =
+        pool_state.get_first_initialized_tick_array(tickarray_bitmap_extension, zero_for_one)?;
+    let mut current_vaild_tick_array_start_index = first_vaild_tick_array_start_index;
+    let mut tick_array_current = find_tick_array(current_vaild_tick_array_start_index)
+        .map_err(|_| error!(ErrorCode::InvalidFirstTickArrayAccount))?;
+    let mut tick_array_start_indexes = vec![current_vaild_tick_array_start_index];
+
+    // continue swapping as long as we haven't used the entire input/output and haven't
+    // reached the price limit
+    while state.amount_specified_remaining != 0
+        && state.sqrt_price_x64 != sqrt_price_limit_x64
+        && state.tick < tick_math::MAX_TICK
+        && state.tick > tick_math::MIN_TICK
+    {
+        let mut step = StepComputations {
+            sqrt_price_start_x64: state.sqrt_price_x64,
+            ..Default::default()
+        };
+
+        let mut next_initialized_tick = if let Some(tick_state) =
+            tick_array_current.next_initialized_tick(state.tick, tick_spacing, zero_for_one)
+        {
+            *tick_state
+        } else if !is_match_pool_current_tick_array {
+            is_match_pool_current_tick_array = true;
+            *tick_array_current.first_initialized_tick(zero_for_one)?
+        } else {
+            TickState::default()
+        };
+
+        if !next_initialized_tick.is_initialized() {
+            let next_initialized_tickarray_index = pool_state
+                .next_initialized_tick_array_start_index(
+                    tickarray_bitmap_extension,
+                    current_vaild_tick_array_start_index,
+                    zero_for_one,
+                )?
+                .ok_or(ErrorCode::LiquidityInsufficient)?;
+
+            tick_array_current = find_tick_array(next_initialized_tickarray_index)?;
+            tick_array_start_indexes.push(next_initialized_tickarray_index);
+            current_vaild_tick_array_start_index = next_initialized_tickarray_index;
+
+            next_initialized_tick = *tick_array_current.first_initialized_tick(zero_for_one)?;
+        }
+        step.tick_next = next_initialized_tick.tick;
+        step.initialized = next_initialized_tick.is_initialized();
+
+        step.tick_next = step
+            .tick_next
+            .clamp(tick_math::MIN_TICK, tick_math::MAX_TICK);
+        step.sqrt_price_next_x64 = tick_math::get_sqrt_price_at_tick(step.tick_next)?;
+
+        let target_price = if (zero_for_one && step.sqrt_price_next_x64 < sqrt_price_limit_x64)
+            || (!zero_for_one && step.sqrt_price_next_x64 > sqrt_price_limit_x64)
+        {
+            sqrt_price_limit_x64
+        } else {
+            step.sqrt_price_next_x64
+        };
+        let swap_step = swap_math::compute_swap_step(
+            state.sqrt_price_x64,
+            target_price,
+            state.liquidity,
+            state.amount_specified_remaining,
+            amm_config.trade_fee_rate,
+            is_base_input,
+            zero_for_one,
+        )?;
+        state.sqrt_price_x64 = swap_step.sqrt_price_next_x64;
+        step.amount_in = swap_step.amount_in;
+        step.amount_out = swap_step.amount_out;
+        step.fee_amount = swap_step.fee_amount;
+
+        if is_base_input {
+            state.amount_specified_remaining = state
+                .amount_specified_remaining
+                .checked_sub(
+                    step.amount_in
+                        .checked_add(step.fee_amount)
+                        .ok_or(ErrorCode::CalculateOverflow)?,
+                )
+                .ok_or(ErrorCode::CalculateOverflow)?;
+            state.amount_calculated = state
+                .amount_calculated
+                .checked_add(step.amount_out)
+                .ok_or(ErrorCode::CalculateOverflow)?;
+        } else {
+            state.amount_specified_remaining = state
+                .amount_specified_remaining
+                .checked_sub(step.amount_out)
+                .ok_or(ErrorCode::CalculateOverflow)?;
+
+            let step_amount_calculate = step
+                .amount_in
+                .checked_add(step.fee_amount)
+                .ok_or(ErrorCode::CalculateOverflow)?;
+            state.amount_calculated = state
+                .amount_calculated
+                .checked_add(step_amount_calculate)
+                .ok_or(ErrorCode::CalculateOverflow)?;
+        }
+        total_fee_amount = total_fee_amount
+            .checked_add(step.fee_amount)
+            .ok_or(ErrorCode::CalculateOverflow)?;
+
+        let step_fee_amount = step.fee_amount;
+        // if the protocol fee is on, calculate how much is owed, decrement fee_amount, and increment protocol_fee
+        if amm_config.protocol_fee_rate > 0 {
+            let delta = U128::from(step_fee_amount)
+                .checked_mul(amm_config.protocol_fee_rate.into())
+                .unwrap()
+                .checked_div(FEE_RATE_DENOMINATOR_VALUE.into())
+                .unwrap()
+                .as_u64();
+            step.fee_amount = step.fee_amount.checked_sub(delta).unwrap();
+            state.protocol_fee = state.protocol_fee.checked_add(delta).unwrap();
+        }
+        // if the fund fee is on, calculate how much is owed, decrement fee_amount, and increment fund_fee
+        if amm_config.fund_fee_rate > 0 {
+            let delta = U128::from(step_fee_amount)
+                .checked_mul(amm_config.fund_fee_rate.into())
+                .unwrap()
+                .checked_div(FEE_RATE_DENOMINATOR_VALUE.into())
+                .unwrap()
+                .as_u64();
+            step.fee_amount = step.fee_amount.checked_sub(delta).unwrap();
+            state.fund_fee = state.fund_fee.checked_add(delta).unwrap();
+        }
+
+        // update global fee tracker
+        if state.liquidity > 0 {
+            let fee_growth_global_x64_delta = U128::from(step.fee_amount)
+                .mul_div_floor(U128::from(fixed_point_64::Q64), U128::from(state.liquidity))
+                .unwrap()
+                .as_u128();
+
+            state.fee_growth_global_x64 = state
+                .fee_growth_global_x64
+                .checked_add(fee_growth_global_x64_delta)
+                .unwrap();
+            state.fee_amount = state.fee_amount.checked_add(step.fee_amount).unwrap();
+        }
+        // shift tick if we reached the next price
+        if state.sqrt_price_x64 == step.sqrt_price_next_x64 {
+            // if the tick is initialized, run the tick transition
+            if step.initialized {
+                let mut liquidity_net = next_initialized_tick.liquidity_net;
+                if zero_for_one {
+                    liquidity_net = -liquidity_net;
+                }
+                state.liquidity = liquidity_math::add_delta(state.liquidity, liquidity_net)?;
+            }
+
+            state.tick = if zero_for_one {
+                step.tick_next - 1
+            } else {
+                step.tick_next
+            };
+        } else if state.sqrt_price_x64 != step.sqrt_price_start_x64 {
+            // recompute unless we're on a lower tick boundary (i.e. already transitioned ticks), and haven't moved
+            state.tick = tick_math::get_tick_at_sqrt_price(state.sqrt_price_x64)?;
+        }
+    }
+
+    let (amount_in, amount_out) = if is_base_input {
+        (
+            amount_specified - state.amount_specified_remaining,
+            state.amount_calculated,
+        )
+    } else {
+        (
+            state.amount_calculated,
+            amount_specified - state.amount_specified_remaining,
+        )
+    };
+
+    Ok(SwapResult {
+        amount_in,
+        amount_out,
+        fee_amount: total_fee_amount,
+        protocol_fee: state.protocol_fee,
+        fund_fee: state.fund_fee,
+        sqrt_price_x64: state.sqrt_price_x64,
+        tick_current: state.tick,
+        liquidity: state.liquidity,
+        tick_array_start_indexes,
+    })
+}
+
+/// A pool of a multi-hop route, the output token of a hop is the input token of the next one
+#[derive(Clone)]
+pub struct RouteHop {
+    pub pool_id: Pubkey,
+    pub amm_config: AmmConfig,
+    pub pool_state: PoolState,
+    /// The tick arrays of the pool the swap may walk through, in any order
+    pub tick_arrays: Vec<TickArrayState>,
+    pub tickarray_bitmap_extension: Option<TickArrayBitmapExtension>,
+    pub zero_for_one: bool,
+    /// The user token account receiving the output token of this hop
+    pub output_token_account: Pubkey,
+    /// Epoch transfer fee of the input mint, see `token::get_epoch_transfer_fee`
+    pub input_transfer_fee: Option<TransferFee>,
+    /// Epoch transfer fee of the output mint
+    pub output_transfer_fee: Option<TransferFee>,
+}
+
+impl RouteHop {
+    pub fn input_mint(&self) -> Pubkey {
+        if self.zero_for_one {
+            self.pool_state.token_mint_0
+        } else {
+            self.pool_state.token_mint_1
+        }
+    }
+
+    pub fn output_mint(&self) -> Pubkey {
+        if self.zero_for_one {
+            self.pool_state.token_mint_1
+        } else {
+            self.pool_state.token_mint_0
+        }
+    }
+}
+
+/// The outcome of a simulated `swap_router_base_in`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RouteQuote {
+    pub amount_in: u64,
+    /// Amount of the output token of the last hop received by the user, transfer fee deducted
+    pub amount_out: u64,
+    /// The quote of every hop, in route order
+    pub hops: Vec<TransferFeeSwapQuote>,
+}
+
+/// Simulate a base input swap across `hops` as `swap_router_base_in` does, without price limit.
+/// Every hop is quoted by `quote_swap_with_transfer_fees`, the amount received from a hop is the
+/// amount sent to the next one. `hops` must not be empty and must chain their mints.
+pub fn quote_route_base_in(hops: &[RouteHop], amount_in: u64) -> Result<RouteQuote> {
+    if hops.is_empty()
+        || hops
+            .windows(2)
+            .any(|pair| pair[0].output_mint() != pair[1].input_mint())
+    {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+    let mut amount = amount_in;
+    let mut quotes = Vec::with_capacity(hops.len());
+    for hop in hops {
+        let quote = quote_swap_with_transfer_fees(
+            &hop.amm_config,
+            &hop.pool_state,
+            &hop.tick_arrays,
+            &hop.tickarray_bitmap_extension,
+            amount,
+            0,
+            hop.zero_for_one,
+            true,
+            hop.input_transfer_fee.as_ref(),
+            hop.output_transfer_fee.as_ref(),
+        )?;
+        amount = quote.amount_out;
+        quotes.push(quote);
+    }
+    Ok(RouteQuote {
+        amount_in,
+        amount_out: amount,
+        hops: quotes,
+    })
+}
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// The quote of a base output swap
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExactOutQuote {
+    /// Amount of input token required, fee included
+    pub amount_in: u64,
+    /// Amount of output token received, less than the requested amount when the price limit is hit
+    pub amount_out: u64,
+    /// Trade fee charged on the input token
+    pub fee_amount: u64,
+    /// Difference between the pool price before the swap and the execution price without fee, in bps
+    pub price_impact_bps: u64,
+    /// Whether the swap stopped at the price limit before receiving the requested amount
+    pub is_price_limit_reached: bool,
+    pub swap_result: SwapResult,
+}
+
+/// Quote a base output swap, `amount_out` is the desired output amount, as `amount` of `swap_v2`
+/// with `is_base_input = false`. The maximum input of the instruction can be derived from
+/// `ExactOutQuote::amount_in` with `get_max_amount_in`.
+pub fn quote_exact_out(
+    amm_config: &AmmConfig,
+    pool_state: &PoolState,
+    tick_arrays: &[TickArrayState],
+    tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
+    amount_out: u64,
+    sqrt_price_limit_x64: u128,
+    zero_for_one: bool,
+) -> Result<ExactOutQuote> {
+    let swap_result = swap_internal(
+        amm_config,
+        pool_state,
+        tick_arrays,
+        tickarray_bitmap_extension,
+        amount_out,
+        sqrt_price_limit_x64,
+        zero_for_one,
+        false,
+    )?;
+    Ok(ExactOutQuote {
+        amount_in: swap_result.amount_in,
+        amount_out: swap_result.amount_out,
+        fee_amount: swap_result.fee_amount,
+        price_impact_bps: get_price_impact_bps(
+            pool_state.sqrt_price_x64,
+            swap_result.amount_in - swap_result.fee_amount,
+            swap_result.amount_out,
+            zero_for_one,
+        ),
+        is_price_limit_reached: swap_result.amount_out < amount_out,
+        swap_result,
+    })
+}
+
+/// The shortfall of `amount_out` against the output `amount_in` would get at `sqrt_price_x64`, in bps
+pub fn get_price_impact_bps(
+    sqrt_price_x64: u128,
+    amount_in: u64,
+    amount_out: u64,
+    zero_for_one: bool,
+) -> u64 {
+    let price_x128 = U512::from(sqrt_price_x64) * U512::from(sqrt_price_x64);
+    let expected_amount_out = if zero_for_one {
+        (U512::from(amount_in) * price_x128) >> 128
+    } else {
+        (U512::from(amount_in) << 128) / price_x128
+    };
+    let amount_out = U512::from(amount_out);
+    if expected_amount_out <= amount_out {
+        return 0;
+    }
+    ((expected_amount_out - amount_out) * U512::from(BPS_DENOMINATOR) / expected_amount_out)
+        .as_u64()
+}
+
+/// The maximum input amount accepting `slippage_bps` more than `amount_in`, rounded up,
+/// i.e. the `other_amount_threshold` of a base output swap
+pub fn get_max_amount_in(amount_in: u64, slippage_bps: u64) -> u64 {
+    let amount = (u128::from(amount_in) * u128::from(BPS_DENOMINATOR.saturating_add(slippage_bps)))
+        .div_ceil(u128::from(BPS_DENOMINATOR));
+    u64::try_from(amount).unwrap_or(u64::MAX)
+}
+
+/// The minimum output amount accepting `slippage_bps` less than `amount_out`, rounded down,
+/// i.e. the `other_amount_threshold` of a base input swap
+pub fn get_min_amount_out(amount_out: u64, slippage_bps: u64) -> u64 {
+    let amount = u128::from(amount_out) * u128::from(BPS_DENOMINATOR.saturating_sub(slippage_bps))
+        / u128::from(BPS_DENOMINATOR);
+    amount as u64
+}
+
+/// A swap quote accounting for the Token-2022 transfer fees of the input and output mints, as `swap_v2` charges them
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransferFeeSwapQuote {
+    /// Amount of input token sent by the user, transfer fee included.
+    /// The `other_amount_threshold` of a base output swap bounds this amount.
+    pub amount_in: u64,
+    /// Amount of output token received by the user, transfer fee deducted.
+    /// The `other_amount_threshold` of a base input swap bounds this amount.
+    pub amount_out: u64,
+    pub input_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+    /// The swap inside the pool, without transfer fees
+    pub swap_result: SwapResult,
+}
+
+/// Quote a `swap_v2` where `input_transfer_fee` and `output_transfer_fee` are the epoch transfer fees
+/// of the input and output mints, see `token::get_epoch_transfer_fee`.
+///
+/// For a base input swap the transfer fee is deducted from `amount_specified` before swapping,
+/// for a base output swap `amount_specified` is the amount to receive and is grossed up with the
+/// output transfer fee before swapping. The user then sends the swapped amount grossed up with the
+/// input transfer fee, which may differ from `amount_specified` by the rounding of the fees.
+pub fn quote_swap_with_transfer_fees(
+    amm_config: &AmmConfig,
+    pool_state: &PoolState,
+    tick_arrays: &[TickArrayState],
+    tickarray_bitmap_extension: &Option<TickArrayBitmapExtension>,
+    amount_specified: u64,
+    sqrt_price_limit_x64: u128,
+    zero_for_one: bool,
+    is_base_input: bool,
+    input_transfer_fee: Option<&TransferFee>,
+    output_transfer_fee: Option<&TransferFee>,
+) -> Result<TransferFeeSwapQuote> {
+    let amount_specified_in_pool = if is_base_input {
+        amount_specified - token::get_transfer_fee(input_transfer_fee, amount_specified)?
+    } else {
+        amount_specified
+            .checked_add(token::get_transfer_inverse_fee(
+                output_transfer_fee,
+                amount_specified,
+            )?)
+            .ok_or(ErrorCode::MaxTokenOverflow)?
+    };
+    let swap_result = swap_internal(
+        amm_config,
+        pool_state,
+        tick_arrays,
+        tickarray_bitmap_extension,
+        amount_specified_in_pool,
+        sqrt_price_limit_x64,
+        zero_for_one,
+        is_base_input,
+    )?;
+    // the program transfers in the swapped amount grossed up with its inverse fee
+    let input_transfer_fee =
+        token::get_transfer_inverse_fee(input_transfer_fee, swap_result.amount_in)?;
+    let output_transfer_fee = token::get_transfer_fee(output_transfer_fee, swap_result.amount_out)?;
+    Ok(TransferFeeSwapQuote {
+        amount_in: swap_result
+            .amount_in
+            .checked_add(input_transfer_fee)
+            .ok_or(ErrorCode::MaxTokenOverflow)?,
+        amount_out: swap_result
+            .amount_out
+            .checked_sub(output_transfer_fee)
+            .ok_or(ErrorCode::TransferFeeCalculateNotMatch)?,
+        input_transfer_fee,
+        output_transfer_fee,
+        swap_result,
+    })
+}
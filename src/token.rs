@@ -0,0 +1,168 @@
+//! Token-2022 transfer fee helpers, reproducing the fees the program deducts on transfers of
+//! `vault_0_mint`, `vault_1_mint`, `input_vault_mint` and `output_vault_mint`, and the
+//! Token-2022 extensions accepted on pool and reward mints.
+
+use anchor_lang::prelude::*;
+use anchor_lang::pubkey;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{
+    TransferFee, TransferFeeConfig, MAX_FEE_BASIS_POINTS,
+};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+
+use crate::account::OperationState;
+use crate::error::ErrorCode;
+
+/// The transfer fee of a mint at `epoch`, parsed from the mint account data.
+/// Returns `None` for SPL Token mints and Token-2022 mints without `TransferFeeConfig`.
+pub fn get_epoch_transfer_fee(mint_data: &[u8], epoch: u64) -> Result<Option<TransferFee>> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    Ok(mint
+        .get_extension::<TransferFeeConfig>()
+        .ok()
+        .map(|transfer_fee_config| *transfer_fee_config.get_epoch_fee(epoch)))
+}
+
+/// Calculate the fee for output amount
+pub fn get_transfer_fee(transfer_fee: Option<&TransferFee>, pre_fee_amount: u64) -> Result<u64> {
+    Ok(match transfer_fee {
+        Some(transfer_fee) => transfer_fee
+            .calculate_fee(pre_fee_amount)
+            .ok_or(ErrorCode::TransferFeeCalculateNotMatch)?,
+        None => 0,
+    })
+}
+
+/// Calculate the fee for input amount
+pub fn get_transfer_inverse_fee(
+    transfer_fee: Option<&TransferFee>,
+    post_fee_amount: u64,
+) -> Result<u64> {
+    Ok(match transfer_fee {
+        Some(transfer_fee) => {
+            if u16::from(transfer_fee.transfer_fee_basis_points) == MAX_FEE_BASIS_POINTS {
+                u64::from(transfer_fee.maximum_fee)
+            } else {
+                transfer_fee
+                    .calculate_inverse_fee(post_fee_amount)
+                    .ok_or(ErrorCode::TransferFeeCalculateNotMatch)?
+            }
+        }
+        None => 0,
+    })
+}
+
+/// The amount received when `pre_fee_amount` is transferred, as the tokens sent by
+/// `decrease_liquidity_v2`, `collect_protocol_fee` and `collect_fund_fee`
+pub fn get_amount_after_transfer_fee(
+    transfer_fee: Option<&TransferFee>,
+    pre_fee_amount: u64,
+) -> Result<u64> {
+    Ok(pre_fee_amount
+        .checked_sub(get_transfer_fee(transfer_fee, pre_fee_amount)?)
+        .ok_or(ErrorCode::TransferFeeCalculateNotMatch)?)
+}
+
+/// The amount to transfer so that `post_fee_amount` is received, as the tokens deposited by
+/// `open_position_v2` and `increase_liquidity_v2`
+pub fn get_amount_with_transfer_fee(
+    transfer_fee: Option<&TransferFee>,
+    post_fee_amount: u64,
+) -> Result<u64> {
+    Ok(post_fee_amount
+        .checked_add(get_transfer_inverse_fee(transfer_fee, post_fee_amount)?)
+        .ok_or(ErrorCode::MaxTokenOverflow)?)
+}
+
+/// Token-2022 mint extensions accepted by `create_pool` and `initialize_reward`
+pub const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 4] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+    ExtensionType::InterestBearingConfig,
+];
+
+/// Token-2022 mints accepted by `create_pool` and `initialize_reward` whatever their extensions
+pub const MINT_WHITELIST: [Pubkey; 4] = [
+    pubkey!("HVbpJAQGNpkgBaYBZQBR1t7yFdvaYVp2vCQQfKKEN4tM"),
+    pubkey!("Crn4x1Y2HUKko7ox2EZMT6N2t2ZyH7eKtwkBGVnhEq1g"),
+    pubkey!("FrBfWJ4qE5sCzKm3k3JaAtqZcXUh4LvJygDeketsrsH4"),
+    pubkey!("2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo"),
+];
+
+/// The extensions of a mint and whether the program accepts them
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintCompatibility {
+    /// Whether the mint is owned by the Token-2022 program
+    pub is_token_2022: bool,
+    /// Whether the mint is in `MINT_WHITELIST`
+    pub is_whitelisted: bool,
+    /// All extensions of the mint, empty for SPL Token mints
+    pub extensions: Vec<ExtensionType>,
+    /// Extensions not in `SUPPORTED_MINT_EXTENSIONS`
+    pub unsupported_extensions: Vec<ExtensionType>,
+}
+
+impl MintCompatibility {
+    /// Whether the mint is supported without a `SupportMintAssociated` account,
+    /// because it is whitelisted or by its extensions alone
+    pub fn is_supported(&self) -> bool {
+        self.is_whitelisted || self.unsupported_extensions.is_empty()
+    }
+
+    /// Whether `create_pool` accepts the mint as `token_mint_0` or `token_mint_1`.
+    /// `is_support_mint` is true if the `SupportMintAssociated` of the mint was created by the admin.
+    pub fn is_accepted_by_create_pool(&self, is_support_mint: bool) -> bool {
+        self.is_supported() || is_support_mint
+    }
+
+    /// Whether `initialize_reward` accepts the mint as `reward_token_mint`, which also
+    /// allows mints in the `whitelist_mints` of the operation account
+    pub fn is_accepted_by_initialize_reward(
+        &self,
+        mint: &Pubkey,
+        is_support_mint: bool,
+        operation_state: &OperationState,
+    ) -> bool {
+        self.is_accepted_by_create_pool(is_support_mint)
+            || operation_state.validate_whitelist_mint(mint)
+    }
+}
+
+/// List the extensions of a mint from its address, owner program and account data, checking them
+/// against `MINT_WHITELIST` and `SUPPORTED_MINT_EXTENSIONS` as `util::is_supported_mint` of the program does
+pub fn check_mint_compatibility(
+    mint: &Pubkey,
+    mint_owner: &Pubkey,
+    mint_data: &[u8],
+) -> Result<MintCompatibility> {
+    if *mint_owner == spl_token::id() {
+        spl_token::state::Mint::unpack(mint_data)?;
+        return Ok(MintCompatibility {
+            is_token_2022: false,
+            is_whitelisted: false,
+            extensions: Vec::new(),
+            unsupported_extensions: Vec::new(),
+        });
+    }
+    if *mint_owner != spl_token_2022::id() {
+        return Err(ProgramError::IncorrectProgramId.into());
+    }
+    let extensions = StateWithExtensions::<Mint>::unpack(mint_data)?.get_extension_types()?;
+    let unsupported_extensions = extensions
+        .iter()
+        .filter(|extension| !SUPPORTED_MINT_EXTENSIONS.contains(extension))
+        .copied()
+        .collect();
+    Ok(MintCompatibility {
+        is_token_2022: true,
+        is_whitelisted: MINT_WHITELIST.contains(mint),
+        extensions,
+        unsupported_extensions,
+    })
+}
@@ -0,0 +1,54 @@
+//! Fixtures shared by the swap tests
+#![allow(dead_code)]
+
+use raydium_clmm_cpi::account::*;
+use raydium_clmm_cpi::libraries::tick_math;
+
+pub const LIQUIDITY: u128 = 1_000_000_000_000;
+pub const INNER_LIQUIDITY: u128 = 4_000_000_000_000;
+
+pub fn set_tick(tick_array: &mut TickArrayState, tick: i32, liquidity_net: i128) {
+    let offset = ((tick - tick_array.start_tick_index) / 10) as usize;
+    tick_array.ticks[offset].tick = tick;
+    tick_array.ticks[offset].liquidity_net = liquidity_net;
+    tick_array.ticks[offset].liquidity_gross = liquidity_net.unsigned_abs();
+    tick_array.initialized_tick_count += 1;
+}
+
+/// A pool at tick 0 with tick spacing 10 and two positions: [-600, 590) and [-120, 120)
+pub fn pool_fixture() -> (AmmConfig, PoolState, Vec<TickArrayState>) {
+    let amm_config = AmmConfig {
+        trade_fee_rate: 2500,
+        protocol_fee_rate: 120_000,
+        fund_fee_rate: 40_000,
+        tick_spacing: 10,
+        ..Default::default()
+    };
+
+    let mut pool_state = PoolState {
+        tick_spacing: 10,
+        tick_current: 0,
+        sqrt_price_x64: tick_math::get_sqrt_price_at_tick(0).unwrap(),
+        liquidity: LIQUIDITY + INNER_LIQUIDITY,
+        ..Default::default()
+    };
+    for start_index in [-600i32, 0] {
+        let bit = (start_index / 600 + 512) as usize;
+        pool_state.tick_array_bitmap[bit / 64] |= 1 << (bit % 64);
+    }
+
+    let mut lower = TickArrayState {
+        start_tick_index: -600,
+        ..Default::default()
+    };
+    set_tick(&mut lower, -600, LIQUIDITY as i128);
+    set_tick(&mut lower, -120, INNER_LIQUIDITY as i128);
+    let mut upper = TickArrayState {
+        start_tick_index: 0,
+        ..Default::default()
+    };
+    set_tick(&mut upper, 120, -(INNER_LIQUIDITY as i128));
+    set_tick(&mut upper, 590, -(LIQUIDITY as i128));
+
+    (amm_config, pool_state, vec![lower, upper])
+}
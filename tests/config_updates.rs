@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::system_program;
+use raydium_clmm_cpi::client::instructions::*;
+use raydium_clmm_cpi::pda;
+
+/// Anchor instruction discriminator, `sha256("global:<name>")[..8]`
+fn discriminator(name: &str) -> Vec<u8> {
+    hash(format!("global:{name}").as_bytes()).to_bytes()[..8].to_vec()
+}
+
+/// `update_amm_config` data, the param byte and the little endian `u32` value
+fn amm_config_data(param: u8, value: u32) -> Vec<u8> {
+    let mut data = discriminator("update_amm_config");
+    data.push(param);
+    data.extend(value.to_le_bytes());
+    data
+}
+
+/// Builds `update`, checks the instruction data and returns the remaining accounts
+fn amm_config_update(update: AmmConfigUpdate, data: Vec<u8>) -> Vec<AccountMeta> {
+    let owner = Pubkey::new_unique();
+    let amm_config = Pubkey::new_unique();
+    let instruction = update_amm_config(owner, amm_config, update);
+    assert_eq!(instruction.program_id, raydium_clmm_cpi::id());
+    assert_eq!(instruction.data, data);
+    assert_eq!(
+        instruction.accounts[..2],
+        [
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(amm_config, false)
+        ]
+    );
+    instruction.accounts[2..].to_vec()
+}
+
+#[test]
+fn update_trade_fee_rate() {
+    let accounts = amm_config_update(
+        AmmConfigUpdate::TradeFeeRate(2500),
+        amm_config_data(0, 2500),
+    );
+    assert!(accounts.is_empty());
+}
+
+#[test]
+fn update_protocol_fee_rate() {
+    let accounts = amm_config_update(
+        AmmConfigUpdate::ProtocolFeeRate(120_000),
+        amm_config_data(1, 120_000),
+    );
+    assert!(accounts.is_empty());
+}
+
+#[test]
+fn update_fund_fee_rate() {
+    let accounts = amm_config_update(
+        AmmConfigUpdate::FundFeeRate(40_000),
+        amm_config_data(2, 40_000),
+    );
+    assert!(accounts.is_empty());
+}
+
+#[test]
+fn update_owner() {
+    let new_owner = Pubkey::new_unique();
+    let accounts = amm_config_update(AmmConfigUpdate::NewOwner(new_owner), amm_config_data(3, 0));
+    assert_eq!(accounts, [AccountMeta::new_readonly(new_owner, false)]);
+}
+
+#[test]
+fn update_fund_owner() {
+    let new_fund_owner = Pubkey::new_unique();
+    let accounts = amm_config_update(
+        AmmConfigUpdate::NewFundOwner(new_fund_owner),
+        amm_config_data(4, 0),
+    );
+    assert_eq!(accounts, [AccountMeta::new_readonly(new_fund_owner, false)]);
+}
+
+/// Builds `update` and checks the accounts and the instruction data, the param byte
+/// followed by the borsh `Vec<Pubkey>` of the keys
+fn assert_operation_update(update: OperationUpdate, param: u8, keys: &[Pubkey]) {
+    let owner = Pubkey::new_unique();
+    let instruction = update_operation_account(owner, update);
+    assert_eq!(
+        instruction.accounts,
+        [
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(pda::find_operation_address().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    );
+    let mut data = discriminator("update_operation_account");
+    data.push(param);
+    data.extend((keys.len() as u32).to_le_bytes());
+    for key in keys {
+        data.extend(key.to_bytes());
+    }
+    assert_eq!(instruction.data, data);
+}
+
+#[test]
+fn add_operation_owners() {
+    let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+    assert_operation_update(OperationUpdate::AddOwners(keys.to_vec()), 0, &keys);
+}
+
+#[test]
+fn remove_operation_owners() {
+    let keys = [Pubkey::new_unique()];
+    assert_operation_update(OperationUpdate::RemoveOwners(keys.to_vec()), 1, &keys);
+}
+
+#[test]
+fn add_whitelist_mints() {
+    let keys = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    assert_operation_update(OperationUpdate::AddWhitelistMints(keys.to_vec()), 2, &keys);
+}
+
+#[test]
+fn remove_whitelist_mints() {
+    assert_operation_update(OperationUpdate::RemoveWhitelistMints(vec![]), 3, &[]);
+}
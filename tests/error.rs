@@ -0,0 +1,47 @@
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::prelude::*;
+use raydium_clmm_cpi::error::ErrorCode;
+
+#[test]
+fn all_errors_are_indexed_by_their_code() {
+    for (index, error) in ErrorCode::ALL.into_iter().enumerate() {
+        let code = u32::from(error);
+        assert_eq!(code, ERROR_CODE_OFFSET + index as u32, "{error:?}");
+        assert_eq!(ErrorCode::from_u32(code), Some(error));
+    }
+    assert!(ErrorCode::from_u32(ERROR_CODE_OFFSET + ErrorCode::ALL.len() as u32).is_none());
+    assert!(ErrorCode::from_u32(ERROR_CODE_OFFSET - 1).is_none());
+    assert!(ErrorCode::from_u32(0).is_none());
+}
+
+#[test]
+fn known_program_error_codes() {
+    // 0x1786 is returned by swaps failing the slippage check
+    assert_eq!(
+        ErrorCode::from_u32(0x1786),
+        Some(ErrorCode::TooLittleOutputReceived)
+    );
+    assert_eq!(u32::from(ErrorCode::LOK), 6000);
+    assert_eq!(u32::from(ErrorCode::TransferFeeCalculateNotMatch), 6044);
+}
+
+#[test]
+fn errors_round_trip_through_anchor_errors() {
+    let error: Error = ErrorCode::InvalidRewardIndex.into();
+    assert_eq!(
+        ErrorCode::from_anchor_error(&error),
+        Some(ErrorCode::InvalidRewardIndex)
+    );
+
+    // The custom program error returned by a failed CPI
+    let error: Error = ProgramError::Custom(ErrorCode::NotApproved.into()).into();
+    assert_eq!(
+        ErrorCode::from_anchor_error(&error),
+        Some(ErrorCode::NotApproved)
+    );
+
+    let error: Error = ProgramError::InvalidAccountData.into();
+    assert!(ErrorCode::from_anchor_error(&error).is_none());
+    let error: Error = anchor_lang::error::ErrorCode::AccountNotInitialized.into();
+    assert!(ErrorCode::from_anchor_error(&error).is_none());
+}
@@ -0,0 +1,313 @@
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::*;
+use anchor_lang::Event;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use raydium_clmm_cpi::client::events::*;
+use raydium_clmm_cpi::event::*;
+
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+/// An aggregator routing swaps through the clmm
+const ROUTER_PROGRAM: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+
+/// A `SwapEvent` logged by a swap of 1 SOL for 150.25 USDC, encoded by hand from the event
+/// discriminator `sha256("event:SwapEvent")[..8]` and the borsh layout of the fields
+const SWAP_EVENT_LOG: &str = "Program data: QMbN6CYIceJ05wCcEx+FajBi2hyyXuwbtRiRc1/fR1bb/Xwn9WYvgDXELwR6AKCgDi5eUC/UyrxSO3l5qxEjSZPQp3n8zRJpT/Gd5oSMoL8hbRWvfxMMzjWV6kSzlNnY9gEtko4R3i1VpGi6tchCxp9FOlhjS9xDpoGWSbW8vILH4UMLkvu2VQDKmjsAAAAAAAAAAAAAAAAQovQIAAAAAAAAAAAAAAAAAZ3VqQz/kDtjAAAAAAAAAAB53w2GSHAAAAAAAAAAAAAAtrj//w==";
+
+fn swap_event() -> SwapEvent {
+    SwapEvent {
+        pool_state: pubkey!("8sLbNZoA1cfnvMJLPfp98ZLAnFSYCFApfJKMbiXNLwxj"),
+        sender: pubkey!("4ct7br2vTPzfdmY3S5HLtTxcGSBfn6pnw98hsS6v359A"),
+        token_account_0: pubkey!("6P4tvbzRY6Bh3MiWDHuLqyHywovsRwRpfskPvyeSoHsz"),
+        token_account_1: pubkey!("6mK4Pxs6GhwnessH7CvPivqDYauiHZmAdbEFDpXFk9zt"),
+        amount_0: 1_000_000_000,
+        transfer_fee_0: 0,
+        amount_1: 150_250_000,
+        transfer_fee_1: 0,
+        zero_for_one: true,
+        sqrt_price_x64: 7_150_468_258_484_311_453,
+        liquidity: 123_456_789_012_345,
+        tick: -18250,
+    }
+}
+
+/// The `Program data:` line logged by `emit!`
+fn program_data(event: &impl Event) -> String {
+    format!("Program data: {}", STANDARD.encode(event.data()))
+}
+
+fn invoke(program: &str, depth: u8) -> String {
+    format!("Program {program} invoke [{depth}]")
+}
+
+fn consumed(program: &str) -> String {
+    format!("Program {program} consumed 4645 of 200000 compute units")
+}
+
+fn success(program: &str) -> String {
+    format!("Program {program} success")
+}
+
+/// The logs of a router swapping through the clmm, which transfers with the token program
+/// and then logs `data`. The router logs its own `Program data:` line afterwards.
+fn routed_swap_logs(data: &str) -> Vec<String> {
+    let clmm = raydium_clmm_cpi::id().to_string();
+    vec![
+        invoke(ROUTER_PROGRAM, 1),
+        "Program log: Instruction: Route".to_string(),
+        invoke(&clmm, 2),
+        "Program log: Instruction: SwapV2".to_string(),
+        invoke(TOKEN_PROGRAM, 3),
+        "Program log: Instruction: TransferChecked".to_string(),
+        consumed(TOKEN_PROGRAM),
+        success(TOKEN_PROGRAM),
+        data.to_string(),
+        consumed(&clmm),
+        success(&clmm),
+        program_data(&swap_event()),
+        format!("Program return: {ROUTER_PROGRAM} AQAAAAAAAAA="),
+        consumed(ROUTER_PROGRAM),
+        success(ROUTER_PROGRAM),
+    ]
+}
+
+/// Decodes `event` from its log line, its `emit_cpi` instruction data and the logs of a
+/// routed swap
+fn assert_decoded(event: impl Event, expected: ClmmEvent) {
+    let log = program_data(&event);
+    assert_eq!(ClmmEvent::decode_log(&log), Some(expected.clone()));
+    let cpi_data = [EVENT_IX_TAG_LE.as_slice(), &event.data()].concat();
+    assert_eq!(
+        ClmmEvent::decode_cpi_instruction_data(&cpi_data),
+        Some(expected.clone())
+    );
+    assert_eq!(parse_logs(&routed_swap_logs(&log)), vec![expected]);
+}
+
+#[test]
+fn swap_event_from_a_program_data_log() {
+    assert_eq!(
+        ClmmEvent::decode_log(SWAP_EVENT_LOG),
+        Some(ClmmEvent::Swap(swap_event()))
+    );
+    assert_eq!(program_data(&swap_event()), SWAP_EVENT_LOG);
+    assert_decoded(swap_event(), ClmmEvent::Swap(swap_event()));
+}
+
+#[test]
+fn config_change_event() {
+    let event = ConfigChangeEvent {
+        index: 4,
+        owner: Pubkey::new_unique(),
+        protocol_fee_rate: 120_000,
+        trade_fee_rate: 500,
+        tick_spacing: 10,
+        fund_fee_rate: 40_000,
+        fund_owner: Pubkey::new_unique(),
+    };
+    assert_decoded(event.clone(), ClmmEvent::ConfigChange(event));
+}
+
+#[test]
+fn create_personal_position_event() {
+    let event = CreatePersonalPositionEvent {
+        pool_state: Pubkey::new_unique(),
+        minter: Pubkey::new_unique(),
+        nft_owner: Pubkey::new_unique(),
+        tick_lower_index: -600,
+        tick_upper_index: 600,
+        liquidity: 1_000_000_000_000,
+        deposit_amount_0: 30_000_000,
+        deposit_amount_1: 29_000_000,
+        deposit_amount_0_transfer_fee: 300,
+        deposit_amount_1_transfer_fee: 0,
+    };
+    assert_decoded(event.clone(), ClmmEvent::CreatePersonalPosition(event));
+}
+
+#[test]
+fn increase_liquidity_event() {
+    let event = IncreaseLiquidityEvent {
+        position_nft_mint: Pubkey::new_unique(),
+        liquidity: 5_000_000,
+        amount_0: 1200,
+        amount_1: 3400,
+        amount_0_transfer_fee: 12,
+        amount_1_transfer_fee: 0,
+    };
+    assert_decoded(event.clone(), ClmmEvent::IncreaseLiquidity(event));
+}
+
+#[test]
+fn decrease_liquidity_event() {
+    let event = DecreaseLiquidityEvent {
+        position_nft_mint: Pubkey::new_unique(),
+        liquidity: 5_000_000,
+        decrease_amount_0: 1199,
+        decrease_amount_1: 3399,
+        fee_amount_0: 7,
+        fee_amount_1: 11,
+        reward_amounts: [13, 0, 17],
+        transfer_fee_0: 0,
+        transfer_fee_1: 3,
+    };
+    assert_decoded(event.clone(), ClmmEvent::DecreaseLiquidity(event));
+}
+
+#[test]
+fn liquidity_calculate_event() {
+    let event = LiquidityCalculateEvent {
+        pool_liquidity: 98_765_432_100,
+        pool_sqrt_price_x64: 1 << 64,
+        pool_tick: -1,
+        calc_amount_0: 1000,
+        calc_amount_1: 2000,
+        trade_fee_owed_0: 3,
+        trade_fee_owed_1: 4,
+        transfer_fee_0: 5,
+        transfer_fee_1: 6,
+    };
+    assert_decoded(event.clone(), ClmmEvent::LiquidityCalculate(event));
+}
+
+#[test]
+fn collect_personal_fee_event() {
+    let event = CollectPersonalFeeEvent {
+        position_nft_mint: Pubkey::new_unique(),
+        recipient_token_account_0: Pubkey::new_unique(),
+        recipient_token_account_1: Pubkey::new_unique(),
+        amount_0: 7,
+        amount_1: 11,
+    };
+    assert_decoded(event.clone(), ClmmEvent::CollectPersonalFee(event));
+}
+
+#[test]
+fn update_reward_infos_event() {
+    let event = UpdateRewardInfosEvent {
+        reward_growth_global_x64: [1 << 64, 0, u128::MAX],
+    };
+    assert_decoded(event.clone(), ClmmEvent::UpdateRewardInfos(event));
+}
+
+#[test]
+fn pool_created_event() {
+    let event = PoolCreatedEvent {
+        token_mint_0: pubkey!("So11111111111111111111111111111111111111112"),
+        token_mint_1: pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+        tick_spacing: 1,
+        pool_state: pubkey!("3ucNos4NbumPLZNWztqGHNFFgkHeRMBQAVemeeomsUxv"),
+        sqrt_price_x64: 7_150_468_258_484_311_453,
+        tick: -18250,
+        token_vault_0: pubkey!("4ct7br2vTPzfdmY3S5HLtTxcGSBfn6pnw98hsS6v359A"),
+        token_vault_1: pubkey!("5it83u57VRrVgc51oNV19TTmAJuffPx5GtGwQr7gQNUo"),
+    };
+    assert_decoded(event.clone(), ClmmEvent::PoolCreated(event));
+}
+
+#[test]
+fn collect_protocol_fee_event() {
+    let event = CollectProtocolFeeEvent {
+        pool_state: Pubkey::new_unique(),
+        recipient_token_account_0: Pubkey::new_unique(),
+        recipient_token_account_1: Pubkey::new_unique(),
+        amount_0: 123,
+        amount_1: 456,
+    };
+    assert_decoded(event.clone(), ClmmEvent::CollectProtocolFee(event));
+}
+
+#[test]
+fn liquidity_change_event() {
+    let event = LiquidityChangeEvent {
+        pool_state: Pubkey::new_unique(),
+        tick: 5,
+        tick_lower: -600,
+        tick_upper: 600,
+        liquidity_before: 1000,
+        liquidity_after: 0,
+    };
+    assert_decoded(event.clone(), ClmmEvent::LiquidityChange(event));
+}
+
+#[test]
+fn logs_of_other_programs_are_skipped() {
+    let clmm = raydium_clmm_cpi::id().to_string();
+    // The router logs a clmm event itself, before and after invoking the clmm
+    let mut logs = vec![invoke(ROUTER_PROGRAM, 1), SWAP_EVENT_LOG.to_string()];
+    logs.extend([
+        invoke(&clmm, 2),
+        invoke(TOKEN_PROGRAM, 3),
+        SWAP_EVENT_LOG.to_string(),
+        success(TOKEN_PROGRAM),
+        success(&clmm),
+        SWAP_EVENT_LOG.to_string(),
+        success(ROUTER_PROGRAM),
+    ]);
+    assert!(parse_logs(&logs).is_empty());
+
+    // A top level clmm instruction following the router
+    logs.extend([invoke(&clmm, 1), SWAP_EVENT_LOG.to_string(), success(&clmm)]);
+    assert_eq!(parse_logs(&logs), vec![ClmmEvent::Swap(swap_event())]);
+}
+
+#[test]
+fn failed_inner_invoke_unwinds_the_invoke_stack() {
+    let clmm = raydium_clmm_cpi::id().to_string();
+    let liquidity_event = LiquidityChangeEvent {
+        pool_state: Pubkey::new_unique(),
+        tick: 5,
+        tick_lower: -600,
+        tick_upper: 600,
+        liquidity_before: 0,
+        liquidity_after: 1000,
+    };
+    let logs = vec![
+        invoke(&clmm, 1),
+        program_data(&liquidity_event),
+        invoke(TOKEN_PROGRAM, 2),
+        "Program log: Error: insufficient funds".to_string(),
+        format!("Program {TOKEN_PROGRAM} failed: custom program error: 0x1"),
+        format!("Program {clmm} failed: custom program error: 0x1"),
+        // The logs of the next transaction, the router logs a clmm event itself
+        invoke(ROUTER_PROGRAM, 1),
+        SWAP_EVENT_LOG.to_string(),
+        success(ROUTER_PROGRAM),
+        invoke(&clmm, 1),
+        SWAP_EVENT_LOG.to_string(),
+        success(&clmm),
+    ];
+    // The event logged before the failure is still decoded
+    assert_eq!(
+        parse_logs(&logs),
+        vec![
+            ClmmEvent::LiquidityChange(liquidity_event),
+            ClmmEvent::Swap(swap_event())
+        ]
+    );
+}
+
+#[test]
+fn undecodable_logs_are_skipped() {
+    let swap_data = STANDARD
+        .decode(SWAP_EVENT_LOG.strip_prefix("Program data: ").unwrap())
+        .unwrap();
+    assert_eq!(
+        ClmmEvent::decode_log("Program log: Instruction: Swap"),
+        None
+    );
+    assert_eq!(ClmmEvent::decode_log("Program data: not base64!"), None);
+    // Truncated event data and an unknown discriminator
+    let truncated = format!("Program data: {}", STANDARD.encode(&swap_data[..100]));
+    assert_eq!(ClmmEvent::decode_log(&truncated), None);
+    let mut unknown = swap_data.clone();
+    unknown[0] ^= 1;
+    let unknown = format!("Program data: {}", STANDARD.encode(&unknown));
+    assert_eq!(ClmmEvent::decode_log(&unknown), None);
+    assert_eq!(ClmmEvent::decode(&swap_data[..7]), None);
+    // Instruction data without the event instruction tag
+    assert_eq!(ClmmEvent::decode_cpi_instruction_data(&swap_data), None);
+
+    let clmm = raydium_clmm_cpi::id().to_string();
+    let logs = [invoke(&clmm, 1), truncated, unknown, success(&clmm)];
+    assert!(parse_logs(&logs).is_empty());
+}
@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use anchor_lang::Discriminator;
+use raydium_clmm_cpi::instruction;
+use serde_json::Value;
+
+const IDL: &str = include_str!("../idl/raydium_clmm.json");
+
+macro_rules! discriminators {
+    ($($name:literal => $ix:ident),* $(,)?) => {
+        BTreeMap::from([$(($name, instruction::$ix::DISCRIMINATOR.to_vec())),*])
+    };
+}
+
+/// Discriminators of the instructions exposed by the crate, keyed by their IDL name
+fn crate_discriminators() -> BTreeMap<&'static str, Vec<u8>> {
+    discriminators! {
+        "create_amm_config" => CreateAmmConfig,
+        "update_amm_config" => UpdateAmmConfig,
+        "create_pool" => CreatePool,
+        "update_pool_status" => UpdatePoolStatus,
+        "create_operation_account" => CreateOperationAccount,
+        "update_operation_account" => UpdateOperationAccount,
+        "create_support_mint_associated" => CreateSupportMintAssociated,
+        "transfer_reward_owner" => TransferRewardOwner,
+        "initialize_reward" => InitializeReward,
+        "collect_remaining_rewards" => CollectRemainingRewards,
+        "update_reward_infos" => UpdateRewardInfos,
+        "set_reward_params" => SetRewardParams,
+        "collect_protocol_fee" => CollectProtocolFee,
+        "collect_fund_fee" => CollectFundFee,
+        "open_position" => OpenPosition,
+        "open_position_v2" => OpenPositionV2,
+        "open_position_with_token22_nft" => OpenPositionWithToken22Nft,
+        "close_position" => ClosePosition,
+        "increase_liquidity" => IncreaseLiquidity,
+        "increase_liquidity_v2" => IncreaseLiquidityV2,
+        "decrease_liquidity" => DecreaseLiquidity,
+        "decrease_liquidity_v2" => DecreaseLiquidityV2,
+        "swap" => Swap,
+        "swap_v2" => SwapV2,
+        "swap_router_base_in" => SwapRouterBaseIn,
+    }
+}
+
+fn idl_discriminators() -> BTreeMap<String, Vec<u8>> {
+    let idl: Value = serde_json::from_str(IDL).unwrap();
+    idl["instructions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|ix| {
+            let name = ix["name"].as_str().unwrap().to_string();
+            let discriminator = ix["discriminator"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|byte| byte.as_u64().unwrap() as u8)
+                .collect();
+            (name, discriminator)
+        })
+        .collect()
+}
+
+#[test]
+fn every_idl_instruction_is_exposed() {
+    let exposed = crate_discriminators();
+    for name in idl_discriminators().keys() {
+        assert!(
+            exposed.contains_key(name.as_str()),
+            "{name} is missing from the crate"
+        );
+    }
+}
+
+#[test]
+fn every_exposed_instruction_is_in_idl() {
+    let idl = idl_discriminators();
+    for name in crate_discriminators().keys() {
+        assert!(idl.contains_key(*name), "{name} is missing from the IDL");
+    }
+}
+
+#[test]
+fn instruction_discriminators_match_idl() {
+    let idl = idl_discriminators();
+    for (name, discriminator) in crate_discriminators() {
+        assert_eq!(idl[name], discriminator, "discriminator of {name}");
+    }
+}
@@ -0,0 +1,324 @@
+//! Compare the instructions and account layouts of the crate with the IDL snapshot in
+//! `idl/raydium_clmm.json`. The account discriminators and sizes are checked by `cargo test`,
+//! the instructions and field offsets need the IDL of the crate and are checked by
+//! `cargo test --features idl-build --test idl_snapshot`.
+
+use std::collections::BTreeMap;
+use std::mem::size_of;
+
+use anchor_lang::Discriminator;
+use raydium_clmm_cpi::account::*;
+use serde_json::{json, Value};
+
+const IDL: &str = include_str!("../idl/raydium_clmm.json");
+
+fn idl() -> Value {
+    serde_json::from_str(IDL).unwrap()
+}
+
+/// Drop docs and module paths, which are not part of the layouts
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(key, _)| key != "docs")
+                .map(|(key, value)| match (key.as_str(), value) {
+                    ("name", Value::String(name)) => {
+                        let name = name.rsplit("::").next().unwrap().to_string();
+                        (key, Value::String(name))
+                    }
+                    (_, value) => (key, normalize(value)),
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(normalize).collect()),
+        value => value,
+    }
+}
+
+fn idl_types() -> BTreeMap<String, Value> {
+    idl()["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|ty| {
+            (
+                ty["name"].as_str().unwrap().to_string(),
+                normalize(ty.clone()),
+            )
+        })
+        .collect()
+}
+
+/// Size of an IDL type, all the structs of the program are borsh or packed
+fn type_size(ty: &Value, types: &BTreeMap<String, Value>) -> usize {
+    match ty {
+        Value::String(ty) => match ty.as_str() {
+            "bool" | "u8" | "i8" => 1,
+            "u16" | "i16" => 2,
+            "u32" | "i32" => 4,
+            "u64" | "i64" => 8,
+            "u128" | "i128" => 16,
+            "pubkey" => 32,
+            ty => panic!("unsized type {ty}"),
+        },
+        Value::Object(ty) if ty.contains_key("array") => {
+            let array = ty["array"].as_array().unwrap();
+            type_size(&array[0], types) * array[1].as_u64().unwrap() as usize
+        }
+        Value::Object(ty) if ty.contains_key("defined") => {
+            let name = ty["defined"]["name"].as_str().unwrap();
+            field_offsets(&types[name], types).1
+        }
+        ty => panic!("unsized type {ty}"),
+    }
+}
+
+/// Offsets of the fields of a struct type and the size of the struct
+fn field_offsets(ty: &Value, types: &BTreeMap<String, Value>) -> (Vec<(String, usize)>, usize) {
+    let mut offset = 0;
+    let offsets = ty["type"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| {
+            let field_offset = offset;
+            offset += type_size(&field["type"], types);
+            (field["name"].as_str().unwrap().to_string(), field_offset)
+        })
+        .collect();
+    (offsets, offset)
+}
+
+#[test]
+fn account_discriminators_match_idl() {
+    macro_rules! discriminators {
+        ($($account:ident),*) => {
+            BTreeMap::from([$((stringify!($account), $account::DISCRIMINATOR.to_vec())),*])
+        };
+    }
+    let discriminators = discriminators!(
+        AmmConfig,
+        ObservationState,
+        OperationState,
+        PersonalPositionState,
+        PoolState,
+        ProtocolPositionState,
+        SupportMintAssociated,
+        TickArrayBitmapExtension,
+        TickArrayState
+    );
+    let idl = idl();
+    let idl_accounts = idl["accounts"].as_array().unwrap();
+    assert_eq!(idl_accounts.len(), discriminators.len());
+    for account in idl_accounts {
+        let name = account["name"].as_str().unwrap();
+        let discriminator: Vec<u8> =
+            serde_json::from_value(account["discriminator"].clone()).unwrap();
+        assert_eq!(
+            discriminators[name], discriminator,
+            "discriminator of {name}"
+        );
+    }
+}
+
+#[test]
+fn account_sizes_match_idl() {
+    let types = idl_types();
+    let size = |name: &str| type_size(&json!({ "defined": { "name": name } }), &types);
+
+    // Accounts, with the 8 bytes discriminator
+    for (name, len) in [
+        ("AmmConfig", AmmConfig::LEN),
+        ("PersonalPositionState", PersonalPositionState::LEN),
+        ("ProtocolPositionState", ProtocolPositionState::LEN),
+        ("SupportMintAssociated", SupportMintAssociated::LEN),
+    ] {
+        assert_eq!(8 + size(name), len, "LEN of {name}");
+    }
+    for (name, len, memory_size) in [
+        ("PoolState", PoolState::LEN, size_of::<PoolState>()),
+        (
+            "ObservationState",
+            ObservationState::LEN,
+            size_of::<ObservationState>(),
+        ),
+        (
+            "OperationState",
+            OperationState::LEN,
+            size_of::<OperationState>(),
+        ),
+        (
+            "TickArrayState",
+            TickArrayState::LEN,
+            size_of::<TickArrayState>(),
+        ),
+        (
+            "TickArrayBitmapExtension",
+            TickArrayBitmapExtension::LEN,
+            size_of::<TickArrayBitmapExtension>(),
+        ),
+    ] {
+        assert_eq!(8 + size(name), len, "LEN of {name}");
+        assert_eq!(size(name), memory_size, "size_of of {name}");
+    }
+
+    // Nested types
+    for (name, len) in [
+        ("RewardInfo", RewardInfo::LEN),
+        ("Observation", Observation::LEN),
+        ("TickState", TickState::LEN),
+        ("PositionRewardInfo", PositionRewardInfo::LEN),
+    ] {
+        assert_eq!(size(name), len, "LEN of {name}");
+    }
+    assert_eq!(size("RewardInfo"), size_of::<RewardInfo>());
+    assert_eq!(size("Observation"), size_of::<Observation>());
+    assert_eq!(size("TickState"), size_of::<TickState>());
+}
+
+#[cfg(feature = "idl-build")]
+mod idl_build {
+    use anchor_lang::idl::types::{IdlAccount, IdlTypeDef};
+    use anchor_lang::idl::IdlBuild;
+    use raydium_clmm_cpi::{context, instruction};
+
+    use super::*;
+
+    /// An instruction of the crate in the IDL format, with its accounts and the fields of the
+    /// generated `instruction` struct as arguments
+    fn crate_instruction<T: IdlBuild + Discriminator>(name: &str, accounts: Value) -> Value {
+        let args = T::create_type()
+            .and_then(|ty| {
+                serde_json::to_value(ty).unwrap()["type"]["fields"]
+                    .as_array()
+                    .cloned()
+            })
+            .unwrap_or_default();
+        normalize(json!({
+            "name": name,
+            "discriminator": T::DISCRIMINATOR,
+            "accounts": accounts,
+            "args": args,
+        }))
+    }
+
+    struct CrateIdl {
+        instructions: Vec<Value>,
+        types: BTreeMap<String, IdlTypeDef>,
+    }
+
+    macro_rules! crate_idl {
+        ($($name:literal => $ix:ident($ctx:ident)),* $(,)?) => {{
+            let mut accounts = BTreeMap::<String, IdlAccount>::new();
+            let mut types = BTreeMap::<String, IdlTypeDef>::new();
+            let instructions = vec![$({
+                let ix_accounts =
+                    context::$ctx::__anchor_private_gen_idl_accounts(&mut accounts, &mut types);
+                instruction::$ix::insert_types(&mut types);
+                crate_instruction::<instruction::$ix>(
+                    $name,
+                    serde_json::to_value(ix_accounts).unwrap(),
+                )
+            }),*];
+            let types = types
+                .into_values()
+                .map(|ty| {
+                    let name = ty.name.rsplit("::").next().unwrap().to_string();
+                    (name, ty)
+                })
+                .collect();
+            CrateIdl { instructions, types }
+        }};
+    }
+
+    fn crate_idl() -> CrateIdl {
+        let mut idl = crate_idl! {
+            "create_amm_config" => CreateAmmConfig(CreateAmmConfig),
+            "update_amm_config" => UpdateAmmConfig(UpdateAmmConfig),
+            "create_pool" => CreatePool(CreatePool),
+            "update_pool_status" => UpdatePoolStatus(UpdatePoolStatus),
+            "create_operation_account" => CreateOperationAccount(CreateOperationAccount),
+            "update_operation_account" => UpdateOperationAccount(UpdateOperationAccount),
+            "create_support_mint_associated" => CreateSupportMintAssociated(CreateSupportMintAssociated),
+            "transfer_reward_owner" => TransferRewardOwner(TransferRewardOwner),
+            "initialize_reward" => InitializeReward(InitializeReward),
+            "collect_remaining_rewards" => CollectRemainingRewards(CollectRemainingRewards),
+            "update_reward_infos" => UpdateRewardInfos(UpdateRewardInfos),
+            "set_reward_params" => SetRewardParams(SetRewardParams),
+            "collect_protocol_fee" => CollectProtocolFee(CollectProtocolFee),
+            "collect_fund_fee" => CollectFundFee(CollectFundFee),
+            "open_position" => OpenPosition(OpenPosition),
+            "open_position_v2" => OpenPositionV2(OpenPositionV2),
+            "open_position_with_token22_nft" => OpenPositionWithToken22Nft(OpenPositionWithToken22Nft),
+            "close_position" => ClosePosition(ClosePosition),
+            "increase_liquidity" => IncreaseLiquidity(IncreaseLiquidity),
+            "increase_liquidity_v2" => IncreaseLiquidityV2(IncreaseLiquidityV2),
+            "decrease_liquidity" => DecreaseLiquidity(DecreaseLiquidity),
+            "decrease_liquidity_v2" => DecreaseLiquidityV2(DecreaseLiquidityV2),
+            "swap" => Swap(SwapSingle),
+            "swap_v2" => SwapV2(SwapSingleV2),
+            "swap_router_base_in" => SwapRouterBaseIn(SwapRouterBaseIn),
+        };
+        // Accounts that the contexts only take as `UncheckedAccount`
+        for ty in [SupportMintAssociated::create_type()].into_iter().flatten() {
+            let name = ty.name.rsplit("::").next().unwrap().to_string();
+            idl.types.insert(name, ty);
+        }
+        idl
+    }
+
+    #[test]
+    fn instructions_match_idl() {
+        let idl = idl();
+        let idl_instructions: BTreeMap<_, _> = idl["instructions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ix| (ix["name"].as_str().unwrap(), normalize(ix.clone())))
+            .collect();
+        let crate_instructions = crate_idl().instructions;
+        assert_eq!(crate_instructions.len(), idl_instructions.len());
+
+        for ix in crate_instructions {
+            let name = ix["name"].as_str().unwrap();
+            let expected = &idl_instructions[name];
+            assert_eq!(
+                ix["discriminator"], expected["discriminator"],
+                "discriminator of {name}"
+            );
+            assert_eq!(ix["accounts"], expected["accounts"], "accounts of {name}");
+            assert_eq!(ix["args"], expected["args"], "args of {name}");
+        }
+    }
+
+    #[test]
+    fn field_offsets_match_idl() {
+        let idl_types = idl_types();
+        let crate_types: BTreeMap<String, Value> = crate_idl()
+            .types
+            .into_iter()
+            .map(|(name, ty)| (name, normalize(serde_json::to_value(ty).unwrap())))
+            .collect();
+
+        for (name, ty) in &idl_types {
+            if name.ends_with("Event") {
+                continue;
+            }
+            let crate_ty = crate_types
+                .get(name)
+                .unwrap_or_else(|| panic!("{name} is missing from the crate"));
+            assert_eq!(
+                crate_ty.get("serialization"),
+                ty.get("serialization"),
+                "serialization of {name}"
+            );
+            assert_eq!(crate_ty.get("repr"), ty.get("repr"), "repr of {name}");
+            assert_eq!(
+                field_offsets(crate_ty, &crate_types),
+                field_offsets(ty, &idl_types),
+                "field offsets of {name}"
+            );
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use raydium_clmm_cpi::account::*;
+use raydium_clmm_cpi::client::instructions::*;
+use raydium_clmm_cpi::error::ErrorCode;
+use raydium_clmm_cpi::pda;
+use raydium_clmm_cpi::swap::{RouteHop, RouteQuote, SwapResult, TransferFeeSwapQuote};
+use raydium_clmm_cpi::token::MintCompatibility;
+
+fn assert_error(result: Result<impl std::fmt::Debug>, error: ErrorCode) {
+    assert_eq!(result.unwrap_err(), error.into());
+}
+
+fn pool_with_rewards(reward_count: usize) -> PoolState {
+    let mut pool_state = PoolState {
+        tick_spacing: 10,
+        ..Default::default()
+    };
+    for reward_info in pool_state.reward_infos.iter_mut().take(reward_count) {
+        reward_info.token_mint = Pubkey::new_unique();
+        reward_info.token_vault = Pubkey::new_unique();
+    }
+    pool_state
+}
+
+fn position() -> PersonalPositionState {
+    PersonalPositionState {
+        pool_id: Pubkey::new_unique(),
+        tick_lower_index: -100,
+        tick_upper_index: 100,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn decrease_liquidity_requires_a_recipient_per_reward() {
+    let pool_state = pool_with_rewards(2);
+    let position = position();
+    let recipients = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let build = |recipients: &[Pubkey], v2: bool| {
+        let builder = if v2 {
+            decrease_liquidity_v2
+        } else {
+            decrease_liquidity
+        };
+        builder(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            &pool_state,
+            &position,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            recipients,
+            1,
+            0,
+            0,
+        )
+    };
+
+    let ix = build(&recipients, false).unwrap();
+    let remaining = &ix.accounts[ix.accounts.len() - 4..];
+    assert_eq!(remaining[0].pubkey, pool_state.reward_infos[0].token_vault);
+    assert_eq!(remaining[1].pubkey, recipients[0]);
+    assert_eq!(remaining[2].pubkey, pool_state.reward_infos[1].token_vault);
+    assert_eq!(remaining[3].pubkey, recipients[1]);
+    let ix = build(&recipients, true).unwrap();
+    assert_eq!(
+        ix.accounts.last().unwrap().pubkey,
+        pool_state.reward_infos[1].token_mint
+    );
+
+    for v2 in [false, true] {
+        assert_error(
+            build(&recipients[..1], v2),
+            ErrorCode::InvalidRewardInputAccountNumber,
+        );
+        assert_error(
+            build(&[recipients[0], recipients[1], Pubkey::new_unique()], v2),
+            ErrorCode::InvalidRewardInputAccountNumber,
+        );
+    }
+}
+
+#[test]
+fn swap_requires_a_tick_array() {
+    let pool_state = PoolState::default();
+    for builder in [swap, swap_v2] {
+        let build = |tick_array_start_indexes: &[i32]| {
+            builder(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                &pool_state,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                true,
+                tick_array_start_indexes,
+                1,
+                0,
+                0,
+                true,
+            )
+        };
+        assert!(build(&[0, -600]).is_ok());
+        assert_error(build(&[]), ErrorCode::NotEnoughTickArrayAccount);
+    }
+}
+
+#[test]
+fn swap_router_requires_hops_matching_the_quote() {
+    let hop = RouteHop {
+        pool_id: Pubkey::new_unique(),
+        amm_config: AmmConfig::default(),
+        pool_state: PoolState::default(),
+        tick_arrays: Vec::new(),
+        tickarray_bitmap_extension: None,
+        zero_for_one: true,
+        output_token_account: Pubkey::new_unique(),
+        input_transfer_fee: None,
+        output_transfer_fee: None,
+    };
+    let hop_quote = TransferFeeSwapQuote {
+        swap_result: SwapResult {
+            tick_array_start_indexes: vec![0],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let quote = |hops: Vec<TransferFeeSwapQuote>| RouteQuote {
+        amount_in: 1,
+        amount_out: 1,
+        hops,
+    };
+    let build = |hops: &[RouteHop], quote: &RouteQuote| {
+        swap_router_base_in(Pubkey::new_unique(), Pubkey::new_unique(), hops, quote, 0)
+    };
+    let invalid_argument: Error = ProgramError::InvalidArgument.into();
+
+    let hops = [hop.clone(), hop];
+    assert!(build(&hops, &quote(vec![hop_quote.clone(), hop_quote.clone()])).is_ok());
+    assert_eq!(
+        build(&[], &quote(Vec::new())).unwrap_err(),
+        invalid_argument
+    );
+    assert_eq!(
+        build(&hops, &quote(vec![hop_quote.clone()])).unwrap_err(),
+        invalid_argument
+    );
+    assert_error(
+        build(
+            &hops,
+            &quote(vec![hop_quote, TransferFeeSwapQuote::default()]),
+        ),
+        ErrorCode::NotEnoughTickArrayAccount,
+    );
+}
+
+#[test]
+fn create_pool_appends_support_mints_of_unsupported_mints_only() {
+    let token_mint_0 = Pubkey::new_unique();
+    let token_mint_1 = Pubkey::new_unique();
+    let compatibility = |unsupported_extensions| MintCompatibility {
+        is_token_2022: true,
+        is_whitelisted: false,
+        extensions: vec![
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::TransferHook,
+        ],
+        unsupported_extensions,
+    };
+    let supported = compatibility(vec![]);
+    let unsupported = compatibility(vec![ExtensionType::TransferHook]);
+    let build = |mint_0_compatibility, mint_1_compatibility| {
+        create_pool(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            token_mint_0,
+            token_mint_1,
+            spl_token_2022::id(),
+            spl_token_2022::id(),
+            mint_0_compatibility,
+            mint_1_compatibility,
+            1 << 64,
+            0,
+        )
+    };
+    let account_count = build(None, None).accounts.len();
+    assert_eq!(build(Some(&supported), None).accounts.len(), account_count);
+
+    let accounts = build(None, Some(&unsupported)).accounts;
+    assert_eq!(accounts.len(), account_count + 1);
+    assert_eq!(
+        accounts[account_count],
+        AccountMeta::new_readonly(
+            pda::find_support_mint_associated_address(&token_mint_1).0,
+            false
+        )
+    );
+    let accounts = build(Some(&unsupported), Some(&unsupported)).accounts;
+    assert_eq!(
+        accounts[account_count..],
+        [
+            AccountMeta::new_readonly(
+                pda::find_support_mint_associated_address(&token_mint_0).0,
+                false
+            ),
+            AccountMeta::new_readonly(
+                pda::find_support_mint_associated_address(&token_mint_1).0,
+                false
+            ),
+        ]
+    );
+}
+
+#[test]
+fn reward_builders_require_a_valid_reward_index() {
+    let pool_state = pool_with_rewards(1);
+    let collect = |reward_index| {
+        collect_remaining_rewards(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            &pool_state,
+            reward_index,
+        )
+    };
+    let set_params = |reward_index| {
+        set_reward_params(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            &pool_state,
+            reward_index,
+            0,
+            0,
+            0,
+            Pubkey::new_unique(),
+        )
+    };
+    let instruction = collect(0).unwrap();
+    assert!(instruction
+        .accounts
+        .iter()
+        .any(|account| account.pubkey == pool_state.reward_infos[0].token_vault));
+    assert!(set_params(REWARD_NUM as u8 - 1).is_ok());
+    assert_error(collect(REWARD_NUM as u8), ErrorCode::InvalidRewardIndex);
+    assert_error(set_params(REWARD_NUM as u8), ErrorCode::InvalidRewardIndex);
+    assert_error(set_params(u8::MAX), ErrorCode::InvalidRewardIndex);
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use raydium_clmm_cpi::error::ErrorCode;
+use raydium_clmm_cpi::libraries::fixed_point_64::Q64;
+use raydium_clmm_cpi::libraries::liquidity_math::*;
+
+/// √P = 1 and √P = 1.5
+const SQRT_PRICE_A: u128 = Q64;
+const SQRT_PRICE_B: u128 = Q64 * 3 / 2;
+const LIQUIDITY: u128 = 1_000_000_000_007;
+
+fn assert_error(result: Result<impl std::fmt::Debug>, error: ErrorCode) {
+    assert_eq!(result.unwrap_err(), error.into());
+}
+
+#[test]
+fn delta_amount_0_rounding() {
+    // L * 2^64 * (√Pb - √Pa) / √Pb / √Pa = L / 3
+    assert_eq!(
+        get_delta_amount_0_unsigned(SQRT_PRICE_A, SQRT_PRICE_B, LIQUIDITY, false).unwrap(),
+        333_333_333_335
+    );
+    assert_eq!(
+        get_delta_amount_0_unsigned(SQRT_PRICE_A, SQRT_PRICE_B, LIQUIDITY, true).unwrap(),
+        333_333_333_336
+    );
+    // The order of the prices does not matter
+    assert_eq!(
+        get_delta_amount_0_unsigned(SQRT_PRICE_B, SQRT_PRICE_A, LIQUIDITY, true).unwrap(),
+        333_333_333_336
+    );
+
+    // A tiny range far from 1 rounds to 0 down and to 1 up
+    let sqrt_price_a = 79_226_673_521_066_979_257_578_248;
+    let sqrt_price_b = 79_226_673_533_412_658_158_812_815;
+    assert_eq!(
+        get_delta_amount_0_unsigned(sqrt_price_a, sqrt_price_b, LIQUIDITY, false).unwrap(),
+        0
+    );
+    assert_eq!(
+        get_delta_amount_0_unsigned(sqrt_price_a, sqrt_price_b, LIQUIDITY, true).unwrap(),
+        1
+    );
+}
+
+#[test]
+fn delta_amount_1_rounding() {
+    // L * (√Pb - √Pa) / 2^64 = L / 2
+    assert_eq!(
+        get_delta_amount_1_unsigned(SQRT_PRICE_A, SQRT_PRICE_B, LIQUIDITY, false).unwrap(),
+        500_000_000_003
+    );
+    assert_eq!(
+        get_delta_amount_1_unsigned(SQRT_PRICE_A, SQRT_PRICE_B, LIQUIDITY, true).unwrap(),
+        500_000_000_004
+    );
+    assert_eq!(
+        get_delta_amount_1_unsigned(SQRT_PRICE_A, SQRT_PRICE_A, LIQUIDITY, true).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn signed_delta_amounts_round_up_when_adding() {
+    let liquidity = LIQUIDITY as i128;
+    assert_eq!(
+        get_delta_amount_0_signed(SQRT_PRICE_A, SQRT_PRICE_B, liquidity).unwrap(),
+        333_333_333_336
+    );
+    assert_eq!(
+        get_delta_amount_0_signed(SQRT_PRICE_A, SQRT_PRICE_B, -liquidity).unwrap(),
+        333_333_333_335
+    );
+    assert_eq!(
+        get_delta_amount_1_signed(SQRT_PRICE_A, SQRT_PRICE_B, liquidity).unwrap(),
+        500_000_000_004
+    );
+    assert_eq!(
+        get_delta_amount_1_signed(SQRT_PRICE_A, SQRT_PRICE_B, -liquidity).unwrap(),
+        500_000_000_003
+    );
+}
+
+#[test]
+fn delta_amount_errors() {
+    assert_error(
+        get_delta_amount_1_unsigned(SQRT_PRICE_A, SQRT_PRICE_B, u128::MAX >> 1, false),
+        ErrorCode::MaxTokenOverflow,
+    );
+    assert_error(
+        get_delta_amount_0_unsigned(0, SQRT_PRICE_B, LIQUIDITY, false),
+        ErrorCode::SqrtPriceX64,
+    );
+}
+
+#[test]
+fn liquidity_from_amounts() {
+    let amount = 1_000_000_000;
+    // amount * (√Pa * √Pb / 2^64) / (√Pb - √Pa) = amount * 3
+    assert_eq!(
+        get_liquidity_from_amount_0(SQRT_PRICE_A, SQRT_PRICE_B, amount).unwrap(),
+        3_000_000_000
+    );
+    // amount * 2^64 / (√Pb - √Pa) = amount * 2
+    assert_eq!(
+        get_liquidity_from_amount_1(SQRT_PRICE_B, SQRT_PRICE_A, amount).unwrap(),
+        2_000_000_000
+    );
+
+    // Below, inside and above the range
+    let below = SQRT_PRICE_A - 1;
+    let inside = Q64 * 5 / 4;
+    let above = SQRT_PRICE_B;
+    assert_eq!(
+        get_liquidity_from_amounts(below, SQRT_PRICE_A, SQRT_PRICE_B, amount, 0).unwrap(),
+        3_000_000_000
+    );
+    // Token_0 gives 7.5e9 and token_1 gives 4e9, the minimum is kept
+    assert_eq!(
+        get_liquidity_from_amounts(inside, SQRT_PRICE_A, SQRT_PRICE_B, amount, amount).unwrap(),
+        4_000_000_000
+    );
+    assert_eq!(
+        get_liquidity_from_amounts(above, SQRT_PRICE_A, SQRT_PRICE_B, 0, amount).unwrap(),
+        2_000_000_000
+    );
+
+    assert_eq!(
+        get_liquidity_from_single_amount_0(inside, SQRT_PRICE_A, SQRT_PRICE_B, amount).unwrap(),
+        7_500_000_000
+    );
+    assert_eq!(
+        get_liquidity_from_single_amount_0(above, SQRT_PRICE_A, SQRT_PRICE_B, amount).unwrap(),
+        0
+    );
+    assert_eq!(
+        get_liquidity_from_single_amount_1(inside, SQRT_PRICE_A, SQRT_PRICE_B, amount).unwrap(),
+        4_000_000_000
+    );
+    assert_eq!(
+        get_liquidity_from_single_amount_1(below, SQRT_PRICE_A, SQRT_PRICE_B, amount).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn liquidity_from_equal_prices_is_an_error() {
+    assert_error(
+        get_liquidity_from_amount_0(SQRT_PRICE_A, SQRT_PRICE_A, 1),
+        ErrorCode::CalculateOverflow,
+    );
+    assert_error(
+        get_liquidity_from_amount_1(SQRT_PRICE_A, SQRT_PRICE_A, 1),
+        ErrorCode::CalculateOverflow,
+    );
+    assert_error(
+        get_liquidity_from_amounts(SQRT_PRICE_A, SQRT_PRICE_B, SQRT_PRICE_B, 1, 1),
+        ErrorCode::CalculateOverflow,
+    );
+}
+
+#[test]
+fn add_delta_bounds() {
+    assert_eq!(add_delta(10, -4).unwrap(), 6);
+    assert_eq!(add_delta(10, 4).unwrap(), 14);
+    assert_error(add_delta(10, -11), ErrorCode::LiquiditySubValueErr);
+    assert_error(add_delta(u128::MAX, 1), ErrorCode::LiquidityAddValueErr);
+}
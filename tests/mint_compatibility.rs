@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use raydium_clmm_cpi::account::OperationState;
+use raydium_clmm_cpi::token::*;
+
+/// Account type byte following the padded base state of a Token-2022 mint
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const BASE_ACCOUNT_LENGTH: usize = 165;
+
+/// Token-2022 mint data with `extensions`, the program only reads the extension types
+fn token_2022_mint(extensions: &[ExtensionType]) -> Vec<u8> {
+    let mut data = vec![0u8; BASE_ACCOUNT_LENGTH];
+    Mint {
+        is_initialized: true,
+        decimals: 6,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data[..Mint::LEN]);
+    if extensions.is_empty() {
+        data.truncate(Mint::LEN);
+        return data;
+    }
+    data.push(ACCOUNT_TYPE_MINT);
+    for extension in extensions {
+        let value = [0u8; 8];
+        data.extend((*extension as u16).to_le_bytes());
+        data.extend((value.len() as u16).to_le_bytes());
+        data.extend(value);
+    }
+    data
+}
+
+fn spl_token_mint() -> Vec<u8> {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        is_initialized: true,
+        decimals: 6,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+fn check(mint: &Pubkey, extensions: &[ExtensionType]) -> MintCompatibility {
+    check_mint_compatibility(mint, &spl_token_2022::id(), &token_2022_mint(extensions)).unwrap()
+}
+
+#[test]
+fn spl_token_mints_are_supported() {
+    let compatibility =
+        check_mint_compatibility(&Pubkey::new_unique(), &spl_token::id(), &spl_token_mint())
+            .unwrap();
+    assert!(!compatibility.is_token_2022);
+    assert!(compatibility.extensions.is_empty());
+    assert!(compatibility.is_supported());
+}
+
+#[test]
+fn supported_extensions() {
+    assert!(check(&Pubkey::new_unique(), &[]).is_supported());
+    for extension in SUPPORTED_MINT_EXTENSIONS {
+        let compatibility = check(&Pubkey::new_unique(), &[extension]);
+        assert!(compatibility.is_token_2022);
+        assert_eq!(compatibility.extensions, vec![extension]);
+        assert!(compatibility.is_supported(), "{extension:?}");
+    }
+    assert!(check(&Pubkey::new_unique(), &SUPPORTED_MINT_EXTENSIONS).is_supported());
+}
+
+#[test]
+fn unsupported_extensions() {
+    for extension in [
+        ExtensionType::MintCloseAuthority,
+        ExtensionType::NonTransferable,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::TransferHook,
+        ExtensionType::DefaultAccountState,
+        ExtensionType::ConfidentialTransferMint,
+    ] {
+        let compatibility = check(
+            &Pubkey::new_unique(),
+            &[ExtensionType::TransferFeeConfig, extension],
+        );
+        assert_eq!(compatibility.unsupported_extensions, vec![extension]);
+        assert!(!compatibility.is_supported(), "{extension:?}");
+        assert!(!compatibility.is_accepted_by_create_pool(false));
+        // Accepted once the admin created the support mint account
+        assert!(compatibility.is_accepted_by_create_pool(true));
+    }
+}
+
+#[test]
+fn whitelisted_mints_are_supported_whatever_their_extensions() {
+    for mint in MINT_WHITELIST {
+        let compatibility = check(&mint, &[ExtensionType::PermanentDelegate]);
+        assert!(compatibility.is_whitelisted);
+        assert!(compatibility.is_supported());
+        assert!(compatibility.is_accepted_by_create_pool(false));
+    }
+    let compatibility = check(&Pubkey::new_unique(), &[]);
+    assert!(!compatibility.is_whitelisted);
+}
+
+#[test]
+fn reward_mints_in_the_operation_whitelist() {
+    let mint = Pubkey::new_unique();
+    let mut operation_state = OperationState {
+        bump: 0,
+        operation_owners: [Pubkey::default(); 10],
+        whitelist_mints: [Pubkey::default(); 100],
+    };
+    let compatibility = check(&mint, &[ExtensionType::TransferHook]);
+    assert!(!compatibility.is_accepted_by_initialize_reward(&mint, false, &operation_state));
+    operation_state.whitelist_mints[3] = mint;
+    assert!(compatibility.is_accepted_by_initialize_reward(&mint, false, &operation_state));
+}
+
+#[test]
+fn mints_of_other_programs_are_rejected() {
+    let result = check_mint_compatibility(
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &spl_token_mint(),
+    );
+    assert_eq!(result.unwrap_err(), ProgramError::IncorrectProgramId.into());
+}
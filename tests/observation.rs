@@ -0,0 +1,150 @@
+use raydium_clmm_cpi::account::*;
+use raydium_clmm_cpi::libraries::tick_math;
+
+fn observation(block_timestamp: u32, tick_cumulative: i64) -> Observation {
+    Observation {
+        block_timestamp,
+        tick_cumulative,
+        ..Default::default()
+    }
+}
+
+/// Five observations every 15 seconds from 1000 with the ticks 10, -20, 7 and -3 in between
+fn partial_state() -> ObservationState {
+    let mut state = ObservationState {
+        initialized: true,
+        observation_index: 4,
+        ..Default::default()
+    };
+    for (i, (block_timestamp, tick_cumulative)) in [
+        (1000, 0),
+        (1015, 150),
+        (1030, -150),
+        (1045, -45),
+        (1060, -90),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        state.observations[i] = observation(block_timestamp, tick_cumulative);
+    }
+    state
+}
+
+const BLOCK_TIMESTAMP: u32 = 1070;
+const TICK_CURRENT: i32 = 5;
+
+#[test]
+fn tick_cumulative_of_a_partial_buffer() {
+    let state = partial_state();
+    let tick_cumulative =
+        |seconds_ago| state.get_tick_cumulative(BLOCK_TIMESTAMP, TICK_CURRENT, seconds_ago);
+
+    assert_eq!(state.observations_in_order().len(), 5);
+    assert_eq!(state.oldest_window(BLOCK_TIMESTAMP), Some(70));
+    // Extrapolated from the latest observation with the current tick
+    assert_eq!(tick_cumulative(0), Some(-40));
+    assert_eq!(tick_cumulative(10), Some(-90));
+    // Interpolated between two observations
+    assert_eq!(tick_cumulative(20), Some(-60));
+    assert_eq!(tick_cumulative(30), Some(-80));
+    assert_eq!(tick_cumulative(40), Some(-150));
+    assert_eq!(tick_cumulative(62), Some(80));
+    // Up to the oldest observation
+    assert_eq!(tick_cumulative(70), Some(0));
+    assert_eq!(tick_cumulative(71), None);
+}
+
+#[test]
+fn tick_cumulative_interpolation_keeps_the_remainder() {
+    let mut state = ObservationState {
+        initialized: true,
+        observation_index: 1,
+        ..Default::default()
+    };
+    state.observations[0] = observation(1000, 0);
+    state.observations[1] = observation(1015, 10);
+    // 10 * 5 / 15, the average tick of 0.67 is not truncated before the interpolation
+    assert_eq!(state.get_tick_cumulative(1015, 0, 10), Some(3));
+}
+
+#[test]
+fn tick_cumulative_of_a_full_buffer_across_timestamp_wraparound() {
+    // The oldest observation is right after the most recent one and timestamps wrap around u32::MAX
+    let start = u32::MAX - 15 * 50 + 7;
+    let observation_index = 2;
+    let mut state = ObservationState {
+        initialized: true,
+        observation_index: observation_index as u16,
+        ..Default::default()
+    };
+    let mut tick_cumulatives = Vec::new();
+    let mut tick_cumulative = 0i64;
+    for k in 0..OBSERVATION_NUM {
+        let slot = (observation_index + 1 + k) % OBSERVATION_NUM;
+        state.observations[slot] = observation(start.wrapping_add(15 * k as u32), tick_cumulative);
+        tick_cumulatives.push(tick_cumulative);
+        tick_cumulative += 15 * (k as i64 % 7 - 3);
+    }
+    let latest_timestamp = start.wrapping_add(15 * (OBSERVATION_NUM as u32 - 1));
+    let block_timestamp = latest_timestamp + 5;
+
+    let ordered = state.observations_in_order();
+    assert_eq!(ordered.len(), OBSERVATION_NUM);
+    assert_eq!({ ordered[0].block_timestamp }, start);
+    assert_eq!(
+        state.oldest_window(block_timestamp),
+        Some(15 * (OBSERVATION_NUM as u32 - 1) + 5)
+    );
+
+    for k in [0, 1, 49, 50, 51, 98, 99] {
+        let seconds_ago = block_timestamp.wrapping_sub(start.wrapping_add(15 * k as u32));
+        assert_eq!(
+            state.get_tick_cumulative(block_timestamp, 0, seconds_ago),
+            Some(tick_cumulatives[k as usize]),
+            "observation {k}"
+        );
+    }
+    // Between the observations 50 and 51 the tick is 50 % 7 - 3 = -2
+    let seconds_ago = block_timestamp.wrapping_sub(start.wrapping_add(15 * 50 + 6));
+    assert_eq!(
+        state.get_tick_cumulative(block_timestamp, 0, seconds_ago),
+        Some(tick_cumulatives[50] - 12)
+    );
+
+    let window = state.oldest_window(block_timestamp).unwrap();
+    assert!(state
+        .get_tick_cumulative(block_timestamp, 0, window)
+        .is_some());
+    assert_eq!(
+        state.get_tick_cumulative(block_timestamp, 0, window + 1),
+        None
+    );
+}
+
+#[test]
+fn twap_tick_within_the_lookback_window() {
+    let state = partial_state();
+    let twap_tick = |start, end| state.get_twap_tick(BLOCK_TIMESTAMP, TICK_CURRENT, start, end);
+
+    assert_eq!(twap_tick(10, 0), Some(5));
+    assert_eq!(twap_tick(20, 0), Some(1));
+    // 90 / 20 = 4.5 and -10 / 20 = -0.5 are rounded towards negative infinity
+    assert_eq!(twap_tick(40, 20), Some(4));
+    assert_eq!(twap_tick(30, 10), Some(-1));
+    assert_eq!(twap_tick(70, 0), Some(-1));
+
+    // Beyond the oldest observation or an empty window
+    assert_eq!(twap_tick(71, 0), None);
+    assert_eq!(twap_tick(10, 10), None);
+    assert_eq!(twap_tick(10, 20), None);
+
+    assert_eq!(
+        state.get_twap_sqrt_price_x64(BLOCK_TIMESTAMP, TICK_CURRENT, 40, 20),
+        tick_math::get_sqrt_price_at_tick(4).ok()
+    );
+    assert_eq!(
+        ObservationState::default().get_twap_tick(BLOCK_TIMESTAMP, TICK_CURRENT, 10, 0),
+        None
+    );
+}
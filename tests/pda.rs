@@ -0,0 +1,98 @@
+//! The derived addresses are compared with mainnet accounts of the program, they only match
+//! if the seeds are right.
+#![cfg(not(feature = "devnet"))]
+
+use anchor_lang::prelude::*;
+use raydium_clmm_cpi::pda;
+
+const WSOL: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+const USDC: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+const USDT: Pubkey = pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
+const RAY: Pubkey = pubkey!("4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R");
+
+/// WSOL/USDC pool of the amm config 8
+const WSOL_USDC_POOL: Pubkey = pubkey!("3ucNos4NbumPLZNWztqGHNFFgkHeRMBQAVemeeomsUxv");
+
+#[test]
+fn amm_config_addresses() {
+    for (index, address) in [
+        (0, pubkey!("4BLNHtVe942GSs4teSZqGX24xwKNkqU7bGgNn3iUiUpw")),
+        (1, pubkey!("E64NGkDLLCdQ2yFNPcavaKptrEgmiQaNykUuLC1Qgwyp")),
+        (3, pubkey!("A1BBtTYJd4i3xU8D6Tc2FzU6ZN4oXZWXKZnCxwbHXr8x")),
+        (4, pubkey!("9iFER3bpjf1PTTCQCfTRu17EJgvsxo9pVyA9QWwEuX4x")),
+        (5, pubkey!("3XCQJQryqpDvvZBfGxR7CLAw5dpGJ9aa7kt1jRLdyxuZ")),
+        (8, pubkey!("3h2e43PunVA5K34vwKCLHWhZF4aZpyaC9RmxvshGAQpL")),
+    ] {
+        assert_eq!(
+            pda::find_amm_config_address(index).0,
+            address,
+            "index {index}"
+        );
+    }
+}
+
+#[test]
+fn pool_addresses() {
+    let amm_config = |index| pda::find_amm_config_address(index).0;
+    for (amm_config, token_mint_0, token_mint_1, address) in [
+        (amm_config(8), WSOL, USDC, WSOL_USDC_POOL),
+        (
+            amm_config(4),
+            WSOL,
+            USDC,
+            pubkey!("8sLbNZoA1cfnvMJLPfp98ZLAnFSYCFApfJKMbiXNLwxj"),
+        ),
+        (
+            amm_config(4),
+            WSOL,
+            USDT,
+            pubkey!("3nMFwZXwY1s1M5s8vYAHqd4wGs4iSxXE4LRoUMMYqEgF"),
+        ),
+        (
+            amm_config(1),
+            RAY,
+            USDC,
+            pubkey!("61R1ndXxvsWXXkWSyNkCxnzwd3zUNB8Q2ibmkiLPC8ht"),
+        ),
+        (
+            amm_config(0),
+            WSOL,
+            USDC,
+            pubkey!("EXHyQxMSttcvLPwjENnXCPZ8GmLjJYHtNBnAkcFeFKMn"),
+        ),
+    ] {
+        assert_eq!(
+            pda::find_pool_address(&amm_config, &token_mint_0, &token_mint_1).0,
+            address
+        );
+    }
+}
+
+#[test]
+fn pool_vault_addresses() {
+    assert_eq!(
+        pda::find_pool_vault_address(&WSOL_USDC_POOL, &WSOL).0,
+        pubkey!("4ct7br2vTPzfdmY3S5HLtTxcGSBfn6pnw98hsS6v359A")
+    );
+    assert_eq!(
+        pda::find_pool_vault_address(&WSOL_USDC_POOL, &USDC).0,
+        pubkey!("5it83u57VRrVgc51oNV19TTmAJuffPx5GtGwQr7gQNUo")
+    );
+    let pool = pubkey!("8sLbNZoA1cfnvMJLPfp98ZLAnFSYCFApfJKMbiXNLwxj");
+    assert_eq!(
+        pda::find_pool_vault_address(&pool, &WSOL).0,
+        pubkey!("6P4tvbzRY6Bh3MiWDHuLqyHywovsRwRpfskPvyeSoHsz")
+    );
+    assert_eq!(
+        pda::find_pool_vault_address(&pool, &USDC).0,
+        pubkey!("6mK4Pxs6GhwnessH7CvPivqDYauiHZmAdbEFDpXFk9zt")
+    );
+}
+
+#[test]
+fn bitmap_extension_address() {
+    assert_eq!(
+        pda::find_bitmap_extension_address(&WSOL_USDC_POOL).0,
+        pubkey!("4NFvUKqknMpoe6CWTzK758B8ojVLzURL5pC6MtiaJ8TQ")
+    );
+}
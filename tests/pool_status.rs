@@ -0,0 +1,102 @@
+use raydium_clmm_cpi::account::*;
+
+const BITS: [PoolStatusBitIndex; 5] = [
+    PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity,
+    PoolStatusBitIndex::DecreaseLiquidity,
+    PoolStatusBitIndex::CollectFee,
+    PoolStatusBitIndex::CollectReward,
+    PoolStatusBitIndex::Swap,
+];
+
+fn pool(status: u8) -> PoolState {
+    PoolState {
+        status,
+        ..Default::default()
+    }
+}
+
+/// The `is_*_enabled` getters, in the order of `BITS`
+fn enabled(pool_state: &PoolState) -> [bool; 5] {
+    [
+        pool_state.is_open_position_or_increase_liquidity_enabled(),
+        pool_state.is_decrease_liquidity_enabled(),
+        pool_state.is_collect_fee_enabled(),
+        pool_state.is_collect_reward_enabled(),
+        pool_state.is_swap_enabled(),
+    ]
+}
+
+#[test]
+fn everything_is_enabled_by_default() {
+    assert_eq!(PoolStatusBuilder::new().build(), 0);
+    let pool_state = pool(0);
+    assert_eq!(enabled(&pool_state), [true; 5]);
+    for bit in BITS {
+        assert!(pool_state.get_status_by_bit(bit));
+    }
+}
+
+/// Disabling `bit` sets the bit `index` of the status, which only disables its operation
+fn assert_only_disabled(bit: PoolStatusBitIndex, index: usize) {
+    let status = PoolStatusBuilder::new().disable(bit).build();
+    assert_eq!(status, 1 << index);
+
+    let pool_state = pool(status);
+    let mut expected = [true; 5];
+    expected[index] = false;
+    assert_eq!(enabled(&pool_state), expected);
+    for other in BITS {
+        assert_eq!(
+            pool_state.get_status_by_bit(other),
+            other != bit,
+            "{other:?}"
+        );
+    }
+}
+
+#[test]
+fn open_position_or_increase_liquidity_bit() {
+    assert_only_disabled(PoolStatusBitIndex::OpenPositionOrIncreaseLiquidity, 0);
+}
+
+#[test]
+fn decrease_liquidity_bit() {
+    assert_only_disabled(PoolStatusBitIndex::DecreaseLiquidity, 1);
+}
+
+#[test]
+fn collect_fee_bit() {
+    assert_only_disabled(PoolStatusBitIndex::CollectFee, 2);
+}
+
+#[test]
+fn collect_reward_bit() {
+    assert_only_disabled(PoolStatusBitIndex::CollectReward, 3);
+}
+
+#[test]
+fn swap_bit() {
+    assert_only_disabled(PoolStatusBitIndex::Swap, 4);
+}
+
+#[test]
+fn enabling_clears_the_bit() {
+    let all_disabled = BITS
+        .into_iter()
+        .fold(PoolStatusBuilder::new(), PoolStatusBuilder::disable);
+    assert_eq!(all_disabled.build(), 0b1_1111);
+    assert_eq!(enabled(&pool(all_disabled.build())), [false; 5]);
+
+    let status = all_disabled
+        .enable(PoolStatusBitIndex::Swap)
+        .set(PoolStatusBitIndex::CollectFee, PoolStatusBitFlag::Enable)
+        .build();
+    assert_eq!(status, 0b0_1011);
+    assert_eq!(enabled(&pool(status)), [false, false, true, false, true]);
+    // Disabling twice keeps the bit set
+    let status = PoolStatusBuilder::new()
+        .disable(PoolStatusBitIndex::Swap)
+        .set(PoolStatusBitIndex::Swap, PoolStatusBitFlag::Disable)
+        .build();
+    assert_eq!(status, 0b1_0000);
+}
@@ -0,0 +1,138 @@
+use raydium_clmm_cpi::account::*;
+use raydium_clmm_cpi::error::ErrorCode;
+use raydium_clmm_cpi::libraries::fixed_point_64::Q64;
+
+const TICK_LOWER: i32 = -100;
+const TICK_UPPER: i32 = 100;
+const LIQUIDITY: u128 = 1_000_000;
+
+/// A growth of `n` per unit of liquidity, negative values wrap around as on chain
+fn growth(n: i128) -> u128 {
+    (n as u128).wrapping_mul(Q64)
+}
+
+fn tick(tick: i32, fee_growth_outside_x64: u128) -> TickState {
+    TickState {
+        tick,
+        fee_growth_outside_0_x64: fee_growth_outside_x64,
+        fee_growth_outside_1_x64: fee_growth_outside_x64.wrapping_mul(2),
+        ..Default::default()
+    }
+}
+
+fn position(fee_growth_inside_last_x64: u128) -> PersonalPositionState {
+    PersonalPositionState {
+        tick_lower_index: TICK_LOWER,
+        tick_upper_index: TICK_UPPER,
+        liquidity: LIQUIDITY,
+        fee_growth_inside_0_last_x64: fee_growth_inside_last_x64,
+        fee_growth_inside_1_last_x64: fee_growth_inside_last_x64.wrapping_mul(2),
+        token_fees_owed_0: 7,
+        token_fees_owed_1: 11,
+        ..Default::default()
+    }
+}
+
+fn pool(tick_current: i32, fee_growth_global_x64: u128) -> PoolState {
+    PoolState {
+        tick_current,
+        fee_growth_global_0_x64: fee_growth_global_x64,
+        fee_growth_global_1_x64: fee_growth_global_x64.wrapping_mul(2),
+        ..Default::default()
+    }
+}
+
+/// Pending fees with the token_1 growths twice the token_0 ones
+fn pending_fees(
+    position: &PersonalPositionState,
+    tick_current: i32,
+    fee_growth_global_x64: u128,
+    tick_lower_outside_x64: u128,
+    tick_upper_outside_x64: u128,
+) -> (u64, u64) {
+    position
+        .get_pending_fees(
+            &pool(tick_current, fee_growth_global_x64),
+            &tick(TICK_LOWER, tick_lower_outside_x64),
+            &tick(TICK_UPPER, tick_upper_outside_x64),
+        )
+        .unwrap()
+}
+
+#[test]
+fn pending_fees_position_below_current_tick() {
+    // The price is below the range, the growth inside is lower outside - upper outside = 1
+    assert_eq!(
+        pending_fees(&position(0), -200, growth(10), growth(3), growth(2)),
+        (7 + LIQUIDITY as u64, 11 + 2 * LIQUIDITY as u64)
+    );
+    assert_eq!(
+        pending_fees(&position(growth(1)), -200, growth(10), growth(3), growth(2)),
+        (7, 11)
+    );
+}
+
+#[test]
+fn pending_fees_position_inside_current_tick() {
+    // global - lower outside - upper outside = 5, the lower tick itself is in range
+    for tick_current in [TICK_LOWER, 0, TICK_UPPER - 1] {
+        assert_eq!(
+            pending_fees(
+                &position(growth(1)),
+                tick_current,
+                growth(10),
+                growth(3),
+                growth(2)
+            ),
+            (7 + 4 * LIQUIDITY as u64, 11 + 8 * LIQUIDITY as u64)
+        );
+    }
+    // Fees are rounded down
+    assert_eq!(
+        pending_fees(&position(0), 0, Q64 / 3, 0, 0),
+        (7 + 333_333, 11 + 666_666)
+    );
+}
+
+#[test]
+fn pending_fees_position_above_current_tick() {
+    // The price is above the range, the growth inside is upper outside - lower outside = -1
+    // which wraps around, only the difference with the last growth matters
+    assert_eq!(
+        pending_fees(
+            &position(growth(-3)),
+            TICK_UPPER,
+            growth(10),
+            growth(3),
+            growth(2)
+        ),
+        (7 + 2 * LIQUIDITY as u64, 11 + 4 * LIQUIDITY as u64)
+    );
+    assert_eq!(
+        pending_fees(&position(growth(-1)), 500, growth(10), growth(3), growth(2)),
+        (7, 11)
+    );
+}
+
+#[test]
+fn pending_fees_across_fee_growth_wraparound() {
+    // The global growth wrapped around u128::MAX after the lower tick was crossed
+    assert_eq!(
+        pending_fees(&position(growth(1)), 0, growth(1), growth(-2), 0),
+        (7 + 2 * LIQUIDITY as u64, 11 + 4 * LIQUIDITY as u64)
+    );
+    assert_eq!(
+        pending_fees(&position(growth(-1)), 0, growth(1), 0, 0),
+        (7 + 2 * LIQUIDITY as u64, 11 + 4 * LIQUIDITY as u64)
+    );
+}
+
+#[test]
+fn pending_fees_require_the_position_ticks() {
+    let result = position(0).get_pending_fees(
+        &pool(0, growth(10)),
+        &tick(TICK_LOWER, 0),
+        &tick(TICK_UPPER + 10, 0),
+    );
+    assert_eq!(result.unwrap_err(), ErrorCode::InvaildTickIndex.into());
+}
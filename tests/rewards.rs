@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use raydium_clmm_cpi::account::*;
+use raydium_clmm_cpi::libraries::fixed_point_64::Q64;
+
+/// A power of two so that the reward growths are exact
+const LIQUIDITY: u128 = 1 << 20;
+const OPEN_TIME: u64 = 1000;
+const END_TIME: u64 = 2000;
+/// 2 reward tokens per second
+const EMISSIONS_PER_SECOND_X64: u128 = 2 * Q64;
+
+/// A pool with a single reward emitting from `OPEN_TIME` to `END_TIME`, last updated at `OPEN_TIME`
+fn pool(liquidity: u128) -> PoolState {
+    let mut pool_state = PoolState {
+        liquidity,
+        tick_current: 0,
+        ..Default::default()
+    };
+    pool_state.reward_infos[0] = RewardInfo {
+        reward_state: RewardState::Initialized as u8,
+        open_time: OPEN_TIME,
+        end_time: END_TIME,
+        last_update_time: OPEN_TIME,
+        emissions_per_second_x64: EMISSIONS_PER_SECOND_X64,
+        reward_total_emissioned: 10,
+        token_mint: Pubkey::new_unique(),
+        reward_growth_global_x64: Q64,
+        ..Default::default()
+    };
+    pool_state
+}
+
+/// The reward growth per unit of liquidity emitted in `seconds`
+fn growth(seconds: u64) -> u128 {
+    u128::from(seconds) * EMISSIONS_PER_SECOND_X64 / LIQUIDITY
+}
+
+#[test]
+fn reward_infos_before_open_time() {
+    let pool_state = pool(LIQUIDITY);
+    for curr_timestamp in [0, OPEN_TIME - 1, OPEN_TIME] {
+        assert_eq!(
+            pool_state.get_latest_reward_infos(curr_timestamp).unwrap(),
+            pool_state.reward_infos
+        );
+    }
+}
+
+#[test]
+fn reward_infos_while_opening() {
+    let pool_state = pool(LIQUIDITY);
+    let reward_infos = pool_state.get_latest_reward_infos(1500).unwrap();
+    let reward_info = reward_infos[0];
+    assert_eq!({ reward_info.last_update_time }, 1500);
+    assert_eq!({ reward_info.reward_growth_global_x64 }, Q64 + growth(500));
+    assert_eq!({ reward_info.reward_total_emissioned }, 10 + 1000);
+    assert_eq!(reward_info.reward_state, RewardState::Opening as u8);
+
+    // The pool state is left unchanged
+    assert_eq!({ pool_state.reward_infos[0].last_update_time }, OPEN_TIME);
+    // Uninitialized slots are skipped
+    assert_eq!(reward_infos[1], RewardInfo::default());
+    assert_eq!(reward_infos[2], RewardInfo::default());
+}
+
+#[test]
+fn reward_infos_are_clamped_at_end_time() {
+    let pool_state = pool(LIQUIDITY);
+    for curr_timestamp in [END_TIME, END_TIME + 1, 5000] {
+        let reward_info = pool_state.get_latest_reward_infos(curr_timestamp).unwrap()[0];
+        assert_eq!({ reward_info.last_update_time }, END_TIME);
+        assert_eq!({ reward_info.reward_growth_global_x64 }, Q64 + growth(1000));
+        assert_eq!({ reward_info.reward_total_emissioned }, 10 + 2000);
+        assert_eq!(reward_info.reward_state, RewardState::Ended as u8);
+    }
+}
+
+#[test]
+fn reward_infos_without_liquidity_only_advance_last_update_time() {
+    let pool_state = pool(0);
+    let reward_info = pool_state.get_latest_reward_infos(1500).unwrap()[0];
+    assert_eq!({ reward_info.last_update_time }, 1500);
+    assert_eq!({ reward_info.reward_growth_global_x64 }, Q64);
+    assert_eq!({ reward_info.reward_total_emissioned }, 10);
+    assert_eq!(reward_info.reward_state, RewardState::Opening as u8);
+}
+
+fn position(
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    liquidity: u128,
+) -> PersonalPositionState {
+    let mut position = PersonalPositionState {
+        tick_lower_index,
+        tick_upper_index,
+        liquidity,
+        ..Default::default()
+    };
+    position.reward_infos[0] = PositionRewardInfo {
+        growth_inside_last_x64: Q64,
+        reward_amount_owed: 5,
+    };
+    position.reward_infos[1].reward_amount_owed = 3;
+    position
+}
+
+fn pending_rewards(position: &PersonalPositionState, curr_timestamp: u64) -> [u64; REWARD_NUM] {
+    let tick = |tick| TickState {
+        tick,
+        ..Default::default()
+    };
+    position
+        .get_pending_rewards(
+            &pool(LIQUIDITY),
+            &tick(position.tick_lower_index),
+            &tick(position.tick_upper_index),
+            curr_timestamp,
+        )
+        .unwrap()
+}
+
+#[test]
+fn pending_rewards_of_a_position_in_range() {
+    // The position holds all the liquidity of the pool, then half of it
+    assert_eq!(
+        pending_rewards(&position(-100, 100, LIQUIDITY), 1500),
+        [5 + 1000, 3, 0]
+    );
+    assert_eq!(
+        pending_rewards(&position(-100, 100, LIQUIDITY / 2), 1500),
+        [5 + 500, 3, 0]
+    );
+    assert_eq!(
+        pending_rewards(&position(-100, 100, LIQUIDITY), 5000),
+        [5 + 2000, 3, 0]
+    );
+    assert_eq!(
+        pending_rewards(&position(-100, 100, LIQUIDITY), OPEN_TIME),
+        [5, 3, 0]
+    );
+}
+
+#[test]
+fn pending_rewards_of_a_position_out_of_range() {
+    // The ticks were never crossed, the growth inside stays at 0 and nothing is earned
+    let mut above = position(100, 200, LIQUIDITY);
+    above.reward_infos[0].growth_inside_last_x64 = 0;
+    assert_eq!(pending_rewards(&above, 1500), [5, 3, 0]);
+
+    let mut below = position(-200, -100, LIQUIDITY);
+    below.reward_infos[0].growth_inside_last_x64 = 0;
+    assert_eq!(pending_rewards(&below, 1500), [5, 3, 0]);
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee;
+use raydium_clmm_cpi::account::*;
+use raydium_clmm_cpi::swap::*;
+
+mod common;
+use common::*;
+
+/// 1% without cap
+fn uncapped_fee() -> TransferFee {
+    TransferFee {
+        epoch: 0.into(),
+        maximum_fee: u64::MAX.into(),
+        transfer_fee_basis_points: 100.into(),
+    }
+}
+
+fn hop(token_mint_0: Pubkey, token_mint_1: Pubkey) -> RouteHop {
+    let (amm_config, mut pool_state, tick_arrays) = pool_fixture();
+    pool_state.token_mint_0 = token_mint_0;
+    pool_state.token_mint_1 = token_mint_1;
+    RouteHop {
+        pool_id: Pubkey::new_unique(),
+        amm_config,
+        pool_state,
+        tick_arrays,
+        tickarray_bitmap_extension: Some(TickArrayBitmapExtension::default()),
+        zero_for_one: true,
+        output_token_account: Pubkey::new_unique(),
+        input_transfer_fee: None,
+        output_transfer_fee: None,
+    }
+}
+
+#[test]
+fn route_quote_chains_the_hops_and_deducts_transfer_fees() {
+    let [mint_a, mint_b, mint_c] = [(); 3].map(|_| Pubkey::new_unique());
+    // mint b charges a 1% transfer fee
+    let mut first = hop(mint_a, mint_b);
+    first.output_transfer_fee = Some(uncapped_fee());
+    let mut second = hop(mint_b, mint_c);
+    second.input_transfer_fee = Some(uncapped_fee());
+
+    let quote = quote_route_base_in(&[first, second], 990_000).unwrap();
+    assert_eq!(quote.amount_in, 990_000);
+    assert_eq!(quote.hops.len(), 2);
+
+    let first = &quote.hops[0];
+    assert_eq!(first.input_transfer_fee, 0);
+    assert_eq!(first.swap_result.amount_in, 990_000);
+    assert_eq!(first.swap_result.amount_out, 987_524);
+    // ceil(987_524 / 100)
+    assert_eq!(first.output_transfer_fee, 9_876);
+    assert_eq!(first.amount_out, 977_648);
+
+    // The received amount is sent to the second pool and pays the fee again
+    let second = &quote.hops[1];
+    // ceil(977_648 / 100)
+    assert_eq!(second.input_transfer_fee, 9_777);
+    assert_eq!(second.amount_in, 977_648);
+    assert_eq!(second.swap_result.amount_in, 967_871);
+    assert_eq!(second.output_transfer_fee, 0);
+    // 967_871 less the 0.25% trade fee of 2_420, at a price of about 1
+    assert_eq!(second.swap_result.amount_out, 965_450);
+    assert_eq!(second.amount_out, second.swap_result.amount_out);
+    assert_eq!(quote.amount_out, second.amount_out);
+}
+
+#[test]
+fn route_quote_of_a_single_hop_is_a_swap_quote() {
+    let (amm_config, pool_state, tick_arrays) = pool_fixture();
+    let swap_result = swap_internal(
+        &amm_config,
+        &pool_state,
+        &tick_arrays,
+        &Some(TickArrayBitmapExtension::default()),
+        1_000_000,
+        0,
+        true,
+        true,
+    )
+    .unwrap();
+
+    let quote =
+        quote_route_base_in(&[hop(Pubkey::default(), Pubkey::default())], 1_000_000).unwrap();
+    assert_eq!(quote.amount_out, swap_result.amount_out);
+    assert_eq!(quote.hops[0].swap_result, swap_result);
+}
+
+#[test]
+fn route_quote_requires_chained_hops() {
+    let [mint_a, mint_b, mint_c] = [(); 3].map(|_| Pubkey::new_unique());
+    let invalid_argument: Error = ProgramError::InvalidArgument.into();
+
+    assert_eq!(
+        quote_route_base_in(&[], 1_000_000).unwrap_err(),
+        invalid_argument
+    );
+    assert_eq!(
+        quote_route_base_in(&[hop(mint_a, mint_b), hop(mint_c, mint_a)], 1_000_000).unwrap_err(),
+        invalid_argument
+    );
+}
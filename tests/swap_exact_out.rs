@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use raydium_clmm_cpi::account::*;
+use raydium_clmm_cpi::error::ErrorCode;
+use raydium_clmm_cpi::libraries::tick_math;
+use raydium_clmm_cpi::swap::*;
+
+mod common;
+use common::*;
+
+fn quote_fixture(
+    amount_out: u64,
+    sqrt_price_limit_x64: u128,
+    zero_for_one: bool,
+) -> Result<ExactOutQuote> {
+    let (amm_config, pool_state, tick_arrays) = pool_fixture();
+    quote_exact_out(
+        &amm_config,
+        &pool_state,
+        &tick_arrays,
+        &Some(TickArrayBitmapExtension::default()),
+        amount_out,
+        sqrt_price_limit_x64,
+        zero_for_one,
+    )
+}
+
+#[test]
+fn exact_out_within_a_single_tick_range() {
+    let quote = quote_fixture(1_000_000, 0, true).unwrap();
+    assert_eq!(quote.amount_in, 1_002_508);
+    assert_eq!(quote.amount_out, 1_000_000);
+    assert_eq!(quote.fee_amount, 2_507);
+    assert_eq!(quote.price_impact_bps, 0);
+    assert!(!quote.is_price_limit_reached);
+    assert_eq!(quote.swap_result.tick_current, -1);
+    assert_eq!(quote.swap_result.liquidity, LIQUIDITY + INNER_LIQUIDITY);
+    assert_eq!(quote.swap_result.protocol_fee, 300);
+    assert_eq!(quote.swap_result.fund_fee, 100);
+}
+
+#[test]
+fn exact_out_crossing_a_position_boundary() {
+    let quote = quote_fixture(50_000_000_000, 0, true).unwrap();
+    assert_eq!(quote.amount_in, 50_969_412_514);
+    assert_eq!(quote.amount_out, 50_000_000_000);
+    assert_eq!(quote.fee_amount, 127_423_532);
+    assert_eq!(quote.price_impact_bps, 165);
+    assert!(!quote.is_price_limit_reached);
+    assert_eq!(quote.swap_result.tick_current, -529);
+    assert_eq!(quote.swap_result.liquidity, LIQUIDITY);
+    assert_eq!(quote.swap_result.tick_array_start_indexes, vec![0, -600]);
+
+    let quote = quote_fixture(40_000_000_000, 0, false).unwrap();
+    assert_eq!(quote.amount_in, 40_507_818_674);
+    assert_eq!(quote.amount_out, 40_000_000_000);
+    assert_eq!(quote.fee_amount, 101_269_547);
+    assert_eq!(quote.price_impact_bps, 100);
+    assert_eq!(quote.swap_result.tick_current, 324);
+    assert_eq!(quote.swap_result.liquidity, LIQUIDITY);
+    assert_eq!(quote.swap_result.tick_array_start_indexes, vec![0]);
+}
+
+#[test]
+fn exact_out_stops_at_the_price_limit() {
+    let sqrt_price_limit_x64 = tick_math::get_sqrt_price_at_tick(-60).unwrap();
+    let quote = quote_fixture(50_000_000_000, sqrt_price_limit_x64, true).unwrap();
+    assert!(quote.is_price_limit_reached);
+    assert_eq!(quote.amount_in, 15_059_418_862);
+    assert_eq!(quote.amount_out, 14_976_774_779);
+    assert_eq!(quote.fee_amount, 37_648_548);
+    assert_eq!(quote.price_impact_bps, 29);
+    assert_eq!(quote.swap_result.sqrt_price_x64, sqrt_price_limit_x64);
+    assert_eq!(quote.swap_result.tick_current, -60);
+    assert_eq!(quote.swap_result.liquidity, LIQUIDITY + INNER_LIQUIDITY);
+}
+
+#[test]
+fn exact_out_price_limit_on_an_initialized_tick_crosses_it() {
+    let sqrt_price_limit_x64 = tick_math::get_sqrt_price_at_tick(-120).unwrap();
+    let quote = quote_fixture(50_000_000_000, sqrt_price_limit_x64, true).unwrap();
+    assert!(quote.is_price_limit_reached);
+    assert_eq!(quote.amount_in, 30_164_081_549);
+    assert_eq!(quote.amount_out, 29_908_688_802);
+    assert_eq!(quote.swap_result.sqrt_price_x64, sqrt_price_limit_x64);
+    assert_eq!(quote.swap_result.tick_current, -121);
+    assert_eq!(quote.swap_result.liquidity, LIQUIDITY);
+}
+
+#[test]
+fn exact_out_beyond_the_last_initialized_tick_fails() {
+    let error = quote_fixture(60_000_000_000, 0, true).unwrap_err();
+    assert_eq!(error, ErrorCode::LiquidityInsufficient.into());
+}
+
+#[test]
+fn exact_out_rejects_a_price_limit_on_the_wrong_side() {
+    let sqrt_price_limit_x64 = tick_math::get_sqrt_price_at_tick(10).unwrap();
+    let error = quote_fixture(1_000_000, sqrt_price_limit_x64, true).unwrap_err();
+    assert_eq!(error, ErrorCode::SqrtPriceLimitOverflow.into());
+}
+
+#[test]
+fn exact_out_amount_in_buys_the_requested_output() {
+    let (amm_config, pool_state, tick_arrays) = pool_fixture();
+    let extension = Some(TickArrayBitmapExtension::default());
+    for (amount_out, zero_for_one) in [
+        (1_000_000, true),
+        (50_000_000_000, true),
+        (40_000_000_000, false),
+    ] {
+        let quote = quote_fixture(amount_out, 0, zero_for_one).unwrap();
+        let base_input = swap_internal(
+            &amm_config,
+            &pool_state,
+            &tick_arrays,
+            &extension,
+            quote.amount_in,
+            0,
+            zero_for_one,
+            true,
+        )
+        .unwrap();
+        assert!(base_input.amount_out >= amount_out);
+    }
+}
+
+#[test]
+fn slippage_bounds() {
+    assert_eq!(get_max_amount_in(1_000_000, 50), 1_005_000);
+    assert_eq!(get_max_amount_in(999, 1), 1_000);
+    assert_eq!(get_max_amount_in(1_000, 0), 1_000);
+    assert_eq!(get_max_amount_in(u64::MAX, 1), u64::MAX);
+    assert_eq!(get_min_amount_out(1_000_000, 50), 995_000);
+    assert_eq!(get_min_amount_out(1_001, 1), 1_000);
+    assert_eq!(get_min_amount_out(1_000, 10_000), 0);
+    assert_eq!(get_min_amount_out(1_000, 20_000), 0);
+
+    let quote = quote_fixture(1_000_000, 0, true).unwrap();
+    assert_eq!(get_max_amount_in(quote.amount_in, 100), 1_012_534);
+}
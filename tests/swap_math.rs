@@ -0,0 +1,154 @@
+use raydium_clmm_cpi::libraries::fixed_point_64::Q64;
+use raydium_clmm_cpi::libraries::sqrt_price_math::*;
+use raydium_clmm_cpi::libraries::swap_math::*;
+
+/// √P = 1
+const SQRT_PRICE: u128 = Q64;
+const LIQUIDITY: u128 = 1_000_000_000_000;
+const AMOUNT: u64 = 1_000_000_000;
+const FEE_RATE: u32 = 3000;
+
+#[test]
+fn next_sqrt_price_from_amount_1_rounds_down() {
+    // √P ± Δy / L = 1 ± 1 / 1000, 2^64 / 1000 = 18446744073709551.616
+    assert_eq!(
+        get_next_sqrt_price_from_amount_1_rounding_down(SQRT_PRICE, LIQUIDITY, AMOUNT, true),
+        SQRT_PRICE + 18_446_744_073_709_551
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_amount_1_rounding_down(SQRT_PRICE, LIQUIDITY, AMOUNT, false),
+        SQRT_PRICE - 18_446_744_073_709_552
+    );
+}
+
+#[test]
+fn next_sqrt_price_from_amount_0_rounds_up() {
+    // √P * L / (L ± Δx * √P) = 1000 / 1001 and 1000 / 999
+    assert_eq!(
+        get_next_sqrt_price_from_amount_0_rounding_up(SQRT_PRICE, LIQUIDITY, AMOUNT, true),
+        18_428_315_757_951_600_016
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_amount_0_rounding_up(SQRT_PRICE, LIQUIDITY, AMOUNT, false),
+        18_465_209_282_992_544_161
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_amount_0_rounding_up(SQRT_PRICE * 3 / 2, LIQUIDITY, AMOUNT, true),
+        27_628_673_100_912_957_988
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_amount_0_rounding_up(SQRT_PRICE, LIQUIDITY, 0, true),
+        SQRT_PRICE
+    );
+}
+
+#[test]
+fn next_sqrt_price_from_input_and_output() {
+    // Token_0 in and token_1 out move the price down, token_1 in and token_0 out move it up
+    assert_eq!(
+        get_next_sqrt_price_from_input(SQRT_PRICE, LIQUIDITY, AMOUNT, true),
+        18_428_315_757_951_600_016
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_input(SQRT_PRICE, LIQUIDITY, AMOUNT, false),
+        SQRT_PRICE + 18_446_744_073_709_551
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_output(SQRT_PRICE, LIQUIDITY, AMOUNT, true),
+        SQRT_PRICE - 18_446_744_073_709_552
+    );
+    assert_eq!(
+        get_next_sqrt_price_from_output(SQRT_PRICE, LIQUIDITY, AMOUNT, false),
+        18_465_209_282_992_544_161
+    );
+}
+
+fn swap_step(
+    sqrt_price_target_x64: u128,
+    amount_remaining: u64,
+    is_base_input: bool,
+    zero_for_one: bool,
+) -> (u128, u64, u64, u64) {
+    let step = compute_swap_step(
+        SQRT_PRICE,
+        sqrt_price_target_x64,
+        LIQUIDITY,
+        amount_remaining,
+        FEE_RATE,
+        is_base_input,
+        zero_for_one,
+    )
+    .unwrap();
+    (
+        step.sqrt_price_next_x64,
+        step.amount_in,
+        step.amount_out,
+        step.fee_amount,
+    )
+}
+
+#[test]
+fn swap_step_base_input() {
+    // The target is not reached, the remainder of the input is the fee
+    assert_eq!(
+        swap_step(SQRT_PRICE * 9 / 10, AMOUNT, true, true),
+        (
+            18_428_370_987_834_680_440,
+            997_000_000,
+            996_006_981,
+            3_000_000
+        )
+    );
+    assert_eq!(
+        swap_step(SQRT_PRICE * 11 / 10, AMOUNT, true, false),
+        (
+            18_465_135_477_551_040_038,
+            997_000_000,
+            996_006_981,
+            3_000_000
+        )
+    );
+    // The target is reached, the fee is taken on the amount in and rounded up
+    assert_eq!(
+        swap_step(SQRT_PRICE * 999 / 1000, LIQUIDITY as u64, true, true),
+        (
+            SQRT_PRICE * 999 / 1000,
+            1_001_001_002,
+            1_000_000_000,
+            3_012_040
+        )
+    );
+}
+
+#[test]
+fn swap_step_base_output() {
+    // The target is not reached, the exact output is bought
+    assert_eq!(
+        swap_step(SQRT_PRICE * 9 / 10, AMOUNT, false, true),
+        (
+            SQRT_PRICE - 18_446_744_073_709_552,
+            1_001_001_002,
+            1_000_000_000,
+            3_012_040
+        )
+    );
+    assert_eq!(
+        swap_step(SQRT_PRICE * 11 / 10, AMOUNT, false, false),
+        (
+            18_465_209_282_992_544_161,
+            1_001_001_002,
+            1_000_000_000,
+            3_012_040
+        )
+    );
+    // The target is reached before the output is filled
+    assert_eq!(
+        swap_step(SQRT_PRICE * 1001 / 1000, LIQUIDITY as u64, false, false),
+        (
+            SQRT_PRICE * 1001 / 1000,
+            1_000_000_000,
+            999_000_999,
+            3_009_028
+        )
+    );
+}
@@ -0,0 +1,282 @@
+use raydium_clmm_cpi::account::*;
+use raydium_clmm_cpi::client::get_swap_tick_array_addresses;
+use raydium_clmm_cpi::error::ErrorCode;
+use raydium_clmm_cpi::libraries::big_num::U1024;
+use raydium_clmm_cpi::libraries::tick_array_bit_map::*;
+use raydium_clmm_cpi::pda;
+use raydium_clmm_cpi::swap::swap_internal;
+
+mod common;
+
+/// With tick spacing 1 a tick array holds 60 ticks and the default bitmap covers [-30720, 30720)
+const TICK_SPACING: u16 = 1;
+
+fn default_bitmap(start_indexes: &[i32]) -> [u64; 16] {
+    let mut bitmap = [0u64; 16];
+    for start_index in start_indexes {
+        let bit = (start_index / 60 + 512) as usize;
+        bitmap[bit / 64] |= 1 << (bit % 64);
+    }
+    bitmap
+}
+
+/// An extension with the tick arrays 31020 (bit 5 of the first positive bitmap)
+/// and -30900 (bit 509 of the first negative bitmap) initialized
+fn extension() -> TickArrayBitmapExtension {
+    let mut extension = TickArrayBitmapExtension::default();
+    extension.positive_tick_array_bitmap[0][0] |= 1 << 5;
+    extension.negative_tick_array_bitmap[0][509 / 64] |= 1 << (509 % 64);
+    extension
+}
+
+fn pool(start_indexes: &[i32]) -> PoolState {
+    PoolState {
+        tick_spacing: TICK_SPACING,
+        tick_array_bitmap: default_bitmap(start_indexes),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn bitmap_tick_boundary() {
+    assert_eq!(max_tick_in_tickarray_bitmap(TICK_SPACING), 30720);
+    assert_eq!(get_bitmap_tick_boundary(0, TICK_SPACING), (0, 30720));
+    assert_eq!(get_bitmap_tick_boundary(-60, TICK_SPACING), (-30720, 0));
+    assert_eq!(get_bitmap_tick_boundary(-30720, TICK_SPACING), (-30720, 0));
+    assert_eq!(
+        get_bitmap_tick_boundary(30720, TICK_SPACING),
+        (30720, 61440)
+    );
+    assert_eq!(
+        get_bitmap_tick_boundary(-30780, TICK_SPACING),
+        (-61440, -30720)
+    );
+}
+
+#[test]
+fn current_tick_array_in_default_bitmap() {
+    let bitmap = U1024(default_bitmap(&[-60, 0]));
+    let check = |tick| check_current_tick_array_is_initialized(bitmap, tick, TICK_SPACING).unwrap();
+    assert_eq!(check(0), (true, 0));
+    assert_eq!(check(59), (true, 0));
+    assert_eq!(check(-1), (true, -60));
+    assert_eq!(check(-60), (true, -60));
+    assert_eq!(check(60), (false, 60));
+    assert_eq!(check(-61), (false, -120));
+}
+
+#[test]
+fn next_tick_array_in_default_bitmap() {
+    let bitmap = U1024(default_bitmap(&[-600, 0, 1200]));
+    let next = |start_index, zero_for_one| {
+        next_initialized_tick_array_start_index(bitmap, start_index, TICK_SPACING, zero_for_one)
+    };
+    assert_eq!(next(0, true), (true, -600));
+    assert_eq!(next(0, false), (true, 1200));
+    assert_eq!(next(60, true), (true, 0));
+    assert_eq!(next(-600, false), (true, 0));
+    // Nothing further, the boundary of the bitmap is returned
+    assert_eq!(next(-600, true), (false, -30720));
+    assert_eq!(next(1200, false), (false, 30660));
+    assert_eq!(next(30660, false), (false, 30660));
+    assert_eq!(next(-30720, true), (false, -30720));
+}
+
+#[test]
+fn tick_arrays_in_extension() {
+    let extension = extension();
+    assert_eq!(
+        extension
+            .check_tick_array_is_initialized(31020, TICK_SPACING)
+            .unwrap(),
+        (true, 31020)
+    );
+    assert_eq!(
+        extension
+            .check_tick_array_is_initialized(30720, TICK_SPACING)
+            .unwrap(),
+        (false, 30720)
+    );
+    assert_eq!(
+        extension
+            .check_tick_array_is_initialized(-30900, TICK_SPACING)
+            .unwrap(),
+        (true, -30900)
+    );
+    assert_eq!(
+        TickArrayBitmapExtension::check_extension_boundary(0, TICK_SPACING).unwrap_err(),
+        ErrorCode::InvalidTickArrayBoundary.into()
+    );
+
+    let next = |start_index, zero_for_one| {
+        extension
+            .next_initialized_tick_array_from_one_bitmap(start_index, TICK_SPACING, zero_for_one)
+            .unwrap()
+    };
+    assert_eq!(next(30660, false), (true, 31020));
+    assert_eq!(next(31020, false), (false, 61380));
+    assert_eq!(next(-30720, true), (true, -30900));
+    assert_eq!(next(-30900, true), (false, -61440));
+}
+
+#[test]
+fn default_bitmap_range() {
+    let pool_state = pool(&[]);
+    assert_eq!(pool_state.tick_array_start_index_range(), (-30720, 30720));
+    assert!(!pool_state.is_overflow_default_tickarray_bitmap(vec![-30720, 30719]));
+    assert!(pool_state.is_overflow_default_tickarray_bitmap(vec![0, 30720]));
+    assert!(pool_state.is_overflow_default_tickarray_bitmap(vec![-30721]));
+
+    // The default bitmap covers the whole tick range from tick spacing 60
+    let pool_state = PoolState {
+        tick_spacing: 60,
+        ..Default::default()
+    };
+    assert_eq!(pool_state.tick_array_start_index_range(), (-446400, 446400));
+}
+
+fn swap_tick_arrays(start_indexes: &[i32], is_bitmap_extension_needed: bool) -> SwapTickArrays {
+    SwapTickArrays {
+        start_indexes: start_indexes.to_vec(),
+        is_bitmap_extension_needed,
+    }
+}
+
+#[test]
+fn swap_tick_arrays_across_default_bitmap_and_extension() {
+    let pool_state = pool(&[-600, 0]);
+    let extension = Some(extension());
+
+    assert_eq!(
+        pool_state
+            .get_swap_tick_arrays(&extension, true, usize::MAX)
+            .unwrap(),
+        swap_tick_arrays(&[0, -600, -30900], true)
+    );
+    assert_eq!(
+        pool_state
+            .get_swap_tick_arrays(&extension, false, usize::MAX)
+            .unwrap(),
+        swap_tick_arrays(&[0, 31020], true)
+    );
+    // The walk is bounded by the caller
+    assert_eq!(
+        pool_state
+            .get_swap_tick_arrays(&extension, true, 2)
+            .unwrap(),
+        swap_tick_arrays(&[0, -600], false)
+    );
+    assert_eq!(
+        pool_state
+            .get_swap_tick_arrays(&extension, true, 0)
+            .unwrap(),
+        swap_tick_arrays(&[], false)
+    );
+    // or by the tick arrays of a quote
+    assert_eq!(
+        pool_state.swap_tick_arrays(vec![0, -600]),
+        swap_tick_arrays(&[0, -600], false)
+    );
+    assert_eq!(
+        pool_state.swap_tick_arrays(vec![0, 31020]),
+        swap_tick_arrays(&[0, 31020], true)
+    );
+
+    // Without the extension the walk stops at the range of the default bitmap
+    assert_eq!(
+        pool_state
+            .get_swap_tick_arrays(&None, false, usize::MAX)
+            .unwrap(),
+        swap_tick_arrays(&[0], true)
+    );
+    assert_eq!(
+        pool_state
+            .get_swap_tick_arrays(&None, true, usize::MAX)
+            .unwrap(),
+        swap_tick_arrays(&[0, -600], true)
+    );
+    assert_eq!(
+        pool_state.get_swap_tick_arrays(&None, true, 2).unwrap(),
+        swap_tick_arrays(&[0, -600], false)
+    );
+    // Other errors are returned
+    assert_eq!(
+        PoolState {
+            tick_current: 100,
+            ..pool(&[-600])
+        }
+        .get_swap_tick_arrays(
+            &Some(TickArrayBitmapExtension::default()),
+            false,
+            usize::MAX
+        )
+        .unwrap_err(),
+        ErrorCode::InsufficientLiquidityForDirection.into()
+    );
+    assert_eq!(
+        pool_state
+            .next_initialized_tick_array_start_index(&None, 0, false)
+            .unwrap_err(),
+        ErrorCode::MissingTickArrayBitmapExtensionAccount.into()
+    );
+    assert_eq!(
+        pool_state
+            .next_initialized_tick_array_start_index(&extension, 31020, false)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn first_tick_array_when_current_is_not_initialized() {
+    let pool_state = PoolState {
+        tick_current: 100,
+        ..pool(&[-600, 0])
+    };
+    assert_eq!(
+        pool_state
+            .get_first_initialized_tick_array(&Some(extension()), true)
+            .unwrap(),
+        (false, 0)
+    );
+    assert_eq!(
+        pool_state
+            .get_first_initialized_tick_array(&Some(extension()), false)
+            .unwrap(),
+        (false, 31020)
+    );
+    assert_eq!(
+        pool_state
+            .get_first_initialized_tick_array(&Some(TickArrayBitmapExtension::default()), false)
+            .unwrap_err(),
+        ErrorCode::InsufficientLiquidityForDirection.into()
+    );
+}
+
+#[test]
+fn swap_tick_array_addresses_of_a_quote() {
+    let (amm_config, pool_state, tick_arrays) = common::pool_fixture();
+    // Crosses the lower tick of the inner position at -120 and the tick array boundary at 0
+    let swap_result = swap_internal(
+        &amm_config,
+        &pool_state,
+        &tick_arrays,
+        &None,
+        40_000_000_000,
+        0,
+        true,
+        true,
+    )
+    .unwrap();
+    let quoted = swap_result.tick_arrays(&pool_state);
+    assert_eq!(quoted, swap_tick_arrays(&[0, -600], false));
+
+    let pool_id = anchor_lang::prelude::Pubkey::new_unique();
+    assert_eq!(
+        get_swap_tick_array_addresses(&pool_id, &quoted),
+        vec![
+            pda::find_tick_array_address(&pool_id, 0).0,
+            pda::find_tick_array_address(&pool_id, -600).0,
+        ]
+    );
+}
@@ -0,0 +1,108 @@
+use raydium_clmm_cpi::error::ErrorCode;
+use raydium_clmm_cpi::libraries::fixed_point_64::Q64;
+use raydium_clmm_cpi::libraries::tick_math::*;
+
+/// `get_sqrt_price_at_tick` of the program, with `sqrt(1.0001^tick) * 2^64` rounded to the
+/// nearest integer as reference
+const SQRT_PRICES: [(i32, u128, u128); 12] = [
+    (1, 18447666387855957090, 18447666387855959851),
+    (-1, 18445821805675395072, 18445821805675392312),
+    (10, 18455969290605287889, 18455969290605290427),
+    (-10, 18437523468038803493, 18437523468038800958),
+    (100, 18539204128674375874, 18539204128674405812),
+    (1000, 19392480388906522465, 19392480388906836278),
+    (50000, 224699260981858280811, 224699260982037790825),
+    (-50000, 1514390236238525528, 1514390236237315697),
+    (200000, 406113483392345977776134, 406113483393643373014939),
+    (-200000, 837899702512935, 837899702510258),
+    (
+        443635,
+        79222712485061176096288712065,
+        79222712478800779441888593670,
+    ),
+    (-443635, 4295262763, 4295262763),
+];
+
+#[test]
+fn sqrt_price_at_min_and_max_tick() {
+    assert_eq!(
+        get_sqrt_price_at_tick(MIN_TICK).unwrap(),
+        MIN_SQRT_PRICE_X64
+    );
+    assert_eq!(
+        get_sqrt_price_at_tick(MAX_TICK).unwrap(),
+        MAX_SQRT_PRICE_X64
+    );
+    assert_eq!(get_sqrt_price_at_tick(0).unwrap(), Q64);
+
+    // The program reports an out of range tick on either side as an upper overflow
+    assert_eq!(
+        get_sqrt_price_at_tick(MIN_TICK - 1).unwrap_err(),
+        ErrorCode::TickUpperOverflow.into()
+    );
+    assert_eq!(
+        get_sqrt_price_at_tick(MAX_TICK + 1).unwrap_err(),
+        ErrorCode::TickUpperOverflow.into()
+    );
+}
+
+#[test]
+fn sqrt_price_at_tick_vectors() {
+    for (tick, sqrt_price_x64, reference) in SQRT_PRICES {
+        assert_eq!(
+            get_sqrt_price_at_tick(tick).unwrap(),
+            sqrt_price_x64,
+            "tick {tick}"
+        );
+        // The approximation of the program is within 1e-10 of the exact value
+        let error = sqrt_price_x64.abs_diff(reference) as f64 / reference as f64;
+        assert!(error < 1e-10, "tick {tick} error {error}");
+    }
+}
+
+#[test]
+fn tick_at_sqrt_price_boundaries() {
+    assert_eq!(
+        get_tick_at_sqrt_price(MIN_SQRT_PRICE_X64).unwrap(),
+        MIN_TICK
+    );
+    assert_eq!(
+        get_tick_at_sqrt_price(MIN_SQRT_PRICE_X64 + 1).unwrap(),
+        MIN_TICK
+    );
+    assert_eq!(
+        get_tick_at_sqrt_price(MAX_SQRT_PRICE_X64 - 1).unwrap(),
+        MAX_TICK - 1
+    );
+    assert_eq!(get_tick_at_sqrt_price(Q64).unwrap(), 0);
+    assert_eq!(get_tick_at_sqrt_price(Q64 - 1).unwrap(), -1);
+
+    // The price at the max tick can never be reached
+    assert_eq!(
+        get_tick_at_sqrt_price(MAX_SQRT_PRICE_X64).unwrap_err(),
+        ErrorCode::SqrtPriceX64.into()
+    );
+    assert_eq!(
+        get_tick_at_sqrt_price(MIN_SQRT_PRICE_X64 - 1).unwrap_err(),
+        ErrorCode::SqrtPriceX64.into()
+    );
+}
+
+#[test]
+fn tick_round_trip() {
+    let ticks = (MIN_TICK..MAX_TICK)
+        .step_by(997)
+        .chain(MIN_TICK..MIN_TICK + 100)
+        .chain(MAX_TICK - 100..MAX_TICK)
+        .chain(-100..100);
+    for tick in ticks {
+        let sqrt_price_x64 = get_sqrt_price_at_tick(tick).unwrap();
+        assert_eq!(get_tick_at_sqrt_price(sqrt_price_x64).unwrap(), tick);
+        if tick > MIN_TICK {
+            assert_eq!(
+                get_tick_at_sqrt_price(sqrt_price_x64 - 1).unwrap(),
+                tick - 1
+            );
+        }
+    }
+}
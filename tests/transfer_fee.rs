@@ -0,0 +1,241 @@
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{
+    TransferFee, TransferFeeConfig,
+};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use raydium_clmm_cpi::account::*;
+use raydium_clmm_cpi::libraries::tick_math;
+use raydium_clmm_cpi::swap::*;
+use raydium_clmm_cpi::token;
+
+mod common;
+use common::*;
+
+fn transfer_fee(epoch: u64, transfer_fee_basis_points: u16, maximum_fee: u64) -> TransferFee {
+    TransferFee {
+        epoch: epoch.into(),
+        maximum_fee: maximum_fee.into(),
+        transfer_fee_basis_points: transfer_fee_basis_points.into(),
+    }
+}
+
+/// 1% capped at 5000
+fn capped_fee() -> TransferFee {
+    transfer_fee(0, 100, 5000)
+}
+
+/// 1% without cap
+fn uncapped_fee() -> TransferFee {
+    transfer_fee(0, 100, u64::MAX)
+}
+
+#[test]
+fn transfer_fee_is_rounded_up_and_capped() {
+    let fee = capped_fee();
+    assert_eq!(token::get_transfer_fee(None, 10_000).unwrap(), 0);
+    assert_eq!(token::get_transfer_fee(Some(&fee), 0).unwrap(), 0);
+    assert_eq!(token::get_transfer_fee(Some(&fee), 10_000).unwrap(), 100);
+    assert_eq!(token::get_transfer_fee(Some(&fee), 10_001).unwrap(), 101);
+    assert_eq!(token::get_transfer_fee(Some(&fee), 500_000).unwrap(), 5000);
+    assert_eq!(
+        token::get_transfer_fee(Some(&fee), 1_000_000).unwrap(),
+        5000
+    );
+    assert_eq!(
+        token::get_amount_after_transfer_fee(Some(&fee), 10_001).unwrap(),
+        9_900
+    );
+}
+
+#[test]
+fn inverse_transfer_fee_grosses_up_the_received_amount() {
+    let fee = capped_fee();
+    assert_eq!(token::get_transfer_inverse_fee(None, 9_900).unwrap(), 0);
+    // 9_900 is received when 10_000 is sent
+    assert_eq!(
+        token::get_transfer_inverse_fee(Some(&fee), 9_900).unwrap(),
+        100
+    );
+    assert_eq!(
+        token::get_amount_with_transfer_fee(Some(&fee), 9_900).unwrap(),
+        10_000
+    );
+    // Beyond the cap the fee is the maximum fee
+    assert_eq!(
+        token::get_transfer_inverse_fee(Some(&fee), 1_000_000).unwrap(),
+        5000
+    );
+    assert_eq!(
+        token::get_amount_with_transfer_fee(Some(&fee), 1_000_000).unwrap(),
+        1_005_000
+    );
+    // A 100% fee always charges the maximum fee
+    let full_fee = transfer_fee(0, 10_000, 42);
+    assert_eq!(
+        token::get_transfer_inverse_fee(Some(&full_fee), 1).unwrap(),
+        42
+    );
+
+    // Hand-checked: pre-fee = ceil(post * 10000 / 9900), fee = ceil(pre-fee / 100)
+    for (received, fee_amount) in [
+        (1, 1),
+        (99, 1),
+        (100, 2),
+        (9_999, 101),
+        (494_000, 4990),
+        (494_999, 5000),
+    ] {
+        assert_eq!(
+            token::get_transfer_inverse_fee(Some(&fee), received).unwrap(),
+            fee_amount,
+            "received {received}"
+        );
+    }
+
+    for amount in [1, 99, 100, 101, 9_999, 123_456] {
+        let sent = token::get_amount_with_transfer_fee(Some(&fee), amount).unwrap();
+        assert_eq!(
+            token::get_amount_after_transfer_fee(Some(&fee), sent).unwrap(),
+            amount,
+            "amount {amount}"
+        );
+    }
+}
+
+#[test]
+fn epoch_transfer_fee_of_a_mint() {
+    let len = ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])
+        .unwrap();
+    let mut data = vec![0u8; len];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+    config.older_transfer_fee = transfer_fee(0, 100, 5000);
+    config.newer_transfer_fee = transfer_fee(10, 200, 8000);
+    state.base = Mint {
+        is_initialized: true,
+        decimals: 6,
+        ..Default::default()
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+
+    let fee_at = |epoch| {
+        token::get_epoch_transfer_fee(&data, epoch)
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(fee_at(0), transfer_fee(0, 100, 5000));
+    assert_eq!(fee_at(9), transfer_fee(0, 100, 5000));
+    assert_eq!(fee_at(10), transfer_fee(10, 200, 8000));
+    assert_eq!(fee_at(11), transfer_fee(10, 200, 8000));
+
+    // A mint without the extension
+    let mut data = vec![0u8; Mint::LEN];
+    Mint {
+        is_initialized: true,
+        decimals: 6,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    assert_eq!(token::get_epoch_transfer_fee(&data, 0).unwrap(), None);
+}
+
+fn quote(
+    amount_specified: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    input_transfer_fee: Option<&TransferFee>,
+    output_transfer_fee: Option<&TransferFee>,
+) -> TransferFeeSwapQuote {
+    let (amm_config, pool_state, tick_arrays) = pool_fixture();
+    quote_swap_with_transfer_fees(
+        &amm_config,
+        &pool_state,
+        &tick_arrays,
+        &Some(TickArrayBitmapExtension::default()),
+        amount_specified,
+        sqrt_price_limit_x64,
+        true,
+        is_base_input,
+        input_transfer_fee,
+        output_transfer_fee,
+    )
+    .unwrap()
+}
+
+#[test]
+fn base_input_quote_deducts_the_input_fee_before_swapping() {
+    let fee = uncapped_fee();
+    let quote = quote(1_000_000, 0, true, Some(&fee), Some(&fee));
+    // 1% of the input is kept by the mint
+    assert_eq!(quote.input_transfer_fee, 10_000);
+    assert_eq!(quote.swap_result.amount_in, 990_000);
+    assert_eq!(quote.amount_in, 1_000_000);
+    // ceil(987_524 / 100)
+    assert_eq!(quote.swap_result.amount_out, 987_524);
+    assert_eq!(quote.output_transfer_fee, 9_876);
+    assert_eq!(quote.amount_out, 977_648);
+}
+
+#[test]
+fn base_input_quote_charges_the_inverse_fee_of_the_swapped_amount() {
+    let fee = uncapped_fee();
+    let quote = quote(10_001, 0, true, Some(&fee), Some(&fee));
+    // 10_001 loses ceil(100.01) = 101, but 9_900 is received from 10_000
+    assert_eq!(quote.swap_result.amount_in, 9_900);
+    assert_eq!(quote.input_transfer_fee, 100);
+    assert_eq!(quote.amount_in, 10_000);
+    assert_eq!(quote.swap_result.amount_out, 9_874);
+    assert_eq!(quote.output_transfer_fee, 99);
+    assert_eq!(quote.amount_out, 9_775);
+}
+
+#[test]
+fn base_input_quote_stopped_by_the_price_limit_charges_the_swapped_amount() {
+    let fee = uncapped_fee();
+    let quote = quote(
+        10_000_000_000,
+        tick_math::get_sqrt_price_at_tick(-10).unwrap(),
+        true,
+        Some(&fee),
+        None,
+    );
+    assert_eq!(quote.swap_result.tick_current, -10);
+    // 5e12 * (1.0001^5 - 1) plus the 0.25% trade fee
+    assert_eq!(quote.swap_result.amount_in, 2_506_766_969);
+    assert_eq!(quote.swap_result.fee_amount, 6_266_918);
+    // ceil(ceil(2_506_766_969 * 10000 / 9900) / 100)
+    assert_eq!(quote.input_transfer_fee, 25_320_879);
+    assert_eq!(quote.amount_in, 2_532_087_848);
+    assert_eq!(quote.output_transfer_fee, 0);
+    assert_eq!(quote.amount_out, 2_499_250_174);
+}
+
+#[test]
+fn base_output_quote_grosses_up_the_output_and_input() {
+    let fee = uncapped_fee();
+    let quote = quote(990_000, 0, false, Some(&fee), Some(&fee));
+    // 990_000 is received from 1_000_000
+    assert_eq!(quote.output_transfer_fee, 10_000);
+    assert_eq!(quote.swap_result.amount_out, 1_000_000);
+    assert_eq!(quote.amount_out, 990_000);
+    assert_eq!(quote.swap_result.amount_in, 1_002_508);
+    // ceil(ceil(1_002_508 * 10000 / 9900) / 100)
+    assert_eq!(quote.input_transfer_fee, 10_127);
+    assert_eq!(quote.amount_in, 1_012_635);
+}
+
+#[test]
+fn base_output_quote_with_capped_fees() {
+    let fee = capped_fee();
+    let quote = quote(1_000_000, 0, false, Some(&fee), Some(&fee));
+    assert_eq!(quote.output_transfer_fee, 5000);
+    assert_eq!(quote.swap_result.amount_out, 1_005_000);
+    assert_eq!(quote.swap_result.amount_in, 1_007_520);
+    assert_eq!(quote.input_transfer_fee, 5000);
+    assert_eq!(quote.amount_in, 1_012_520);
+    assert_eq!(quote.amount_out, 1_000_000);
+}